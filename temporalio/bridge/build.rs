@@ -0,0 +1,45 @@
+//! Records which sdk-core is compiled into the bridge so it can be reported at
+//! runtime
+
+use std::path::Path;
+use std::process::Command;
+
+const CORE_MANIFEST: &str = "sdk-core/core/Cargo.toml";
+
+fn git(args: &[&str]) -> Option<String> {
+    Command::new("git")
+        .arg("-C")
+        .arg("sdk-core")
+        .args(args)
+        .output()
+        .ok()
+        .filter(|output| output.status.success())
+        .and_then(|output| String::from_utf8(output.stdout).ok())
+        .map(|out| out.trim().to_string())
+}
+
+fn main() {
+    let version = std::fs::read_to_string(CORE_MANIFEST)
+        .ok()
+        .and_then(|manifest| {
+            manifest.lines().find_map(|line| {
+                line.strip_prefix("version = ")
+                    .map(|version| version.trim_matches('"').to_string())
+            })
+        })
+        .unwrap_or_else(|| "unknown".to_string());
+    // Empty when building from a source distribution without git metadata
+    let git_sha = git(&["rev-parse", "HEAD"]).unwrap_or_default();
+    // Otherwise cargo would keep reporting the old values after the submodule
+    // moves. The submodule's HEAD lives in the parent repo's .git directory,
+    // and is relative to the submodule if git gives a relative path.
+    println!("cargo:rerun-if-changed={}", CORE_MANIFEST);
+    if let Some(head) = git(&["rev-parse", "--git-path", "HEAD"]) {
+        println!(
+            "cargo:rerun-if-changed={}",
+            Path::new("sdk-core").join(head).display()
+        );
+    }
+    println!("cargo:rustc-env=TEMPORAL_SDK_CORE_VERSION={}", version);
+    println!("cargo:rustc-env=TEMPORAL_SDK_CORE_GIT_SHA={}", git_sha);
+}
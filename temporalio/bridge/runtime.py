@@ -23,13 +23,43 @@ class Runtime:
             thread_id, exc_type
         )
 
-    def __init__(self, *, telemetry: TelemetryConfig) -> None:
+    @staticmethod
+    def core_version() -> str:
+        """Version of SDK Core compiled into the bridge."""
+        return temporalio.bridge.temporal_sdk_bridge.core_version()
+
+    @staticmethod
+    def core_build_info() -> Dict[str, str]:
+        """SDK Core ``version``, ``git_sha`` (empty if unknown at build time),
+        and ``bridge_version``.
+        """
+        return temporalio.bridge.temporal_sdk_bridge.core_build_info()
+
+    def __init__(
+        self, *, telemetry: TelemetryConfig, worker_threads: Optional[int] = None
+    ) -> None:
         """Create SDK Core runtime."""
-        self._ref = temporalio.bridge.temporal_sdk_bridge.init_runtime(telemetry)
+        self._ref = temporalio.bridge.temporal_sdk_bridge.init_runtime(
+            telemetry, worker_threads
+        )
 
-    def retrieve_buffered_metrics(self, durations_as_seconds: bool) -> Sequence[Any]:
+    def retrieve_buffered_metrics(self) -> Sequence[Any]:
         """Get buffered metrics."""
-        return self._ref.retrieve_buffered_metrics(durations_as_seconds)
+        return self._ref.retrieve_buffered_metrics()
+
+    def counter_add(self, name: str, value: int, tags: Mapping[str, str]) -> None:
+        """Add to a counter by name. Does nothing if metrics are disabled."""
+        self._ref.counter_add(name, value, dict(tags))
+
+    def gauge_set(self, name: str, value: float, tags: Mapping[str, str]) -> None:
+        """Set a gauge by name. Does nothing if metrics are disabled."""
+        self._ref.gauge_set(name, value, dict(tags))
+
+    def histogram_record(
+        self, name: str, value: float, tags: Mapping[str, str]
+    ) -> None:
+        """Record to a histogram by name. Does nothing if metrics are disabled."""
+        self._ref.histogram_record(name, value, dict(tags))
 
     def write_test_info_log(self, message: str, extra_data: str) -> None:
         """Write a test core log at INFO level."""
@@ -46,6 +76,8 @@ class LoggingConfig:
 
     filter: str
     forward_to: Optional[Callable[[Sequence[BufferedLogEntry]], None]]
+    forward_max_batch_size: Optional[int]
+    forward_max_batch_interval_millis: Optional[int]
 
 
 @dataclass(frozen=True)
@@ -55,6 +87,7 @@ class MetricsConfig:
     opentelemetry: Optional[OpenTelemetryConfig]
     prometheus: Optional[PrometheusConfig]
     buffered_with_size: int
+    buffered_durations_as_seconds: bool
     attach_service_name: bool
     global_tags: Optional[Mapping[str, str]]
     metric_prefix: Optional[str]
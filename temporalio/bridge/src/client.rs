@@ -12,10 +12,11 @@ use tonic::metadata::MetadataKey;
 use url::Url;
 
 use crate::runtime;
+use crate::worker;
 
 pyo3::create_exception!(temporal_sdk_bridge, RPCError, PyException);
 
-type Client = RetryClient<ConfiguredClient<TemporalServiceClientWithMetrics>>;
+pub(crate) type Client = RetryClient<ConfiguredClient<TemporalServiceClientWithMetrics>>;
 
 #[pyclass]
 pub struct ClientRef {
@@ -443,8 +444,7 @@ impl ClientRef {
 }
 
 fn rpc_req<P: prost::Message + Default>(call: RpcCall) -> PyResult<tonic::Request<P>> {
-    let proto = P::decode(&*call.req)
-        .map_err(|err| PyValueError::new_err(format!("Invalid proto: {}", err)))?;
+    let proto = P::decode(&*call.req).map_err(worker::invalid_proto::<P>)?;
     let mut req = tonic::Request::new(proto);
     for (k, v) in call.metadata {
         req.metadata_mut().insert(
@@ -467,18 +467,20 @@ where
 {
     match res {
         Ok(resp) => Ok(resp.get_ref().encode_to_vec()),
-        Err(err) => {
-            Err(Python::with_gil(move |py| {
-                // Create tuple of "status", "message", and optional "details"
-                let code = err.code() as u32;
-                let message = err.message().to_owned();
-                let details = err.details().into_py(py);
-                RPCError::new_err((code, message, details))
-            }))
-        }
+        Err(err) => Err(rpc_error(err)),
     }
 }
 
+pub(crate) fn rpc_error(err: tonic::Status) -> PyErr {
+    Python::with_gil(move |py| {
+        // Create tuple of "status", "message", and optional "details"
+        let code = err.code() as u32;
+        let message = err.message().to_owned();
+        let details = err.details().into_py(py);
+        RPCError::new_err((code, message, details))
+    })
+}
+
 impl TryFrom<ClientConfig> for ClientOptions {
     type Error = PyErr;
 
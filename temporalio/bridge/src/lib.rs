@@ -1,5 +1,6 @@
 use pyo3::prelude::*;
 use pyo3::types::PyTuple;
+use std::collections::HashMap;
 
 mod client;
 mod metric;
@@ -32,6 +33,8 @@ fn temporal_sdk_bridge(py: Python, m: &PyModule) -> PyResult<()> {
     m.add_class::<runtime::BufferedLogEntry>()?;
     m.add_function(wrap_pyfunction!(init_runtime, m)?)?;
     m.add_function(wrap_pyfunction!(raise_in_thread, m)?)?;
+    m.add_function(wrap_pyfunction!(core_version, m)?)?;
+    m.add_function(wrap_pyfunction!(core_build_info, m)?)?;
 
     // Testing stuff
     m.add_class::<testing::EphemeralServerRef>()?;
@@ -43,9 +46,28 @@ fn temporal_sdk_bridge(py: Python, m: &PyModule) -> PyResult<()> {
         "PollShutdownError",
         py.get_type::<worker::PollShutdownError>(),
     )?;
+    m.add(
+        "PollTransportError",
+        py.get_type::<worker::PollTransportError>(),
+    )?;
+    m.add(
+        "PollInternalError",
+        py.get_type::<worker::PollInternalError>(),
+    )?;
+    m.add("CompletionError", py.get_type::<worker::CompletionError>())?;
+    m.add(
+        "InvalidProtoError",
+        py.get_type::<worker::InvalidProtoError>(),
+    )?;
     m.add_class::<worker::WorkerRef>()?;
+    m.add_class::<worker::WorkerValidation>()?;
+    m.add_class::<worker::ActivationStream>()?;
     m.add_class::<worker::HistoryPusher>()?;
+    m.add_class::<worker::PollCanceller>()?;
+    m.add_class::<worker::ReplayResultReceiver>()?;
     m.add_class::<worker::CustomSlotSupplier>()?;
+    m.add_class::<worker::ManualSlotSupplier>()?;
+    m.add_class::<worker::SlotEventReceiver>()?;
     m.add_class::<worker::SlotReserveCtx>()?;
     m.add_class::<worker::SlotReleaseCtx>()?;
     m.add_class::<worker::SlotMarkUsedCtx>()?;
@@ -53,7 +75,9 @@ fn temporal_sdk_bridge(py: Python, m: &PyModule) -> PyResult<()> {
     m.add_class::<worker::ActivitySlotInfo>()?;
     m.add_class::<worker::LocalActivitySlotInfo>()?;
     m.add_function(wrap_pyfunction!(new_worker, m)?)?;
+    m.add_function(wrap_pyfunction!(new_worker_dry_run, m)?)?;
     m.add_function(wrap_pyfunction!(new_replay_worker, m)?)?;
+    m.add_function(wrap_pyfunction!(new_replay_worker_pool, m)?)?;
     Ok(())
 }
 
@@ -72,8 +96,12 @@ fn new_metric_meter(runtime_ref: &runtime::RuntimeRef) -> Option<metric::MetricM
 }
 
 #[pyfunction]
-fn init_runtime(telemetry_config: runtime::TelemetryConfig) -> PyResult<runtime::RuntimeRef> {
-    runtime::init_runtime(telemetry_config)
+#[pyo3(signature = (telemetry_config, worker_threads=None))]
+fn init_runtime(
+    telemetry_config: runtime::TelemetryConfig,
+    worker_threads: Option<usize>,
+) -> PyResult<runtime::RuntimeRef> {
+    runtime::init_runtime(telemetry_config, worker_threads)
 }
 
 #[pyfunction]
@@ -81,6 +109,16 @@ fn raise_in_thread(py: Python, thread_id: std::os::raw::c_long, exc: &PyAny) ->
     runtime::raise_in_thread(py, thread_id, exc)
 }
 
+#[pyfunction]
+fn core_version() -> &'static str {
+    runtime::CORE_VERSION
+}
+
+#[pyfunction]
+fn core_build_info() -> HashMap<&'static str, &'static str> {
+    runtime::core_build_info()
+}
+
 #[pyfunction]
 fn start_dev_server<'a>(
     py: Python<'a>,
@@ -109,10 +147,33 @@ fn new_worker(
 }
 
 #[pyfunction]
+fn new_worker_dry_run(
+    runtime_ref: &runtime::RuntimeRef,
+    client: &client::ClientRef,
+    config: worker::WorkerConfig,
+) -> PyResult<()> {
+    worker::new_worker_dry_run(runtime_ref, client, config)
+}
+
+#[pyfunction]
+#[pyo3(signature = (runtime_ref, config, history_buffer_size=1))]
 fn new_replay_worker<'a>(
     py: Python<'a>,
     runtime_ref: &runtime::RuntimeRef,
     config: worker::WorkerConfig,
+    history_buffer_size: usize,
+) -> PyResult<&'a PyTuple> {
+    worker::new_replay_worker(py, runtime_ref, config, history_buffer_size)
+}
+
+#[pyfunction]
+#[pyo3(signature = (runtime_ref, config, concurrency, history_buffer_size=1))]
+fn new_replay_worker_pool<'a>(
+    py: Python<'a>,
+    runtime_ref: &runtime::RuntimeRef,
+    config: &PyAny,
+    concurrency: usize,
+    history_buffer_size: usize,
 ) -> PyResult<&'a PyTuple> {
-    worker::new_replay_worker(py, runtime_ref, config)
+    worker::new_replay_worker_pool(py, runtime_ref, config, concurrency, history_buffer_size)
 }
@@ -1,4 +1,5 @@
 use std::any::Any;
+use std::sync::Mutex;
 use std::time::Duration;
 use std::{collections::HashMap, sync::Arc};
 
@@ -202,6 +203,96 @@ impl MetricGaugeFloatRef {
     }
 }
 
+/// Metric handles created on first use when recording metrics by name directly
+/// on the runtime
+#[derive(Default)]
+pub(crate) struct NamedMetrics {
+    counters: Mutex<HashMap<String, Arc<dyn metrics::Counter>>>,
+    gauges: Mutex<HashMap<String, Arc<dyn metrics::GaugeF64>>>,
+    histograms: Mutex<HashMap<String, Arc<dyn metrics::HistogramF64>>>,
+}
+
+impl NamedMetrics {
+    pub(crate) fn counter_add(
+        &self,
+        meter: &metrics::TemporalMeter,
+        name: String,
+        value: u64,
+        tags: HashMap<String, String>,
+    ) {
+        let counter = self
+            .counters
+            .lock()
+            .unwrap()
+            .entry(name)
+            .or_insert_with_key(|name| {
+                meter
+                    .inner
+                    .counter(build_metric_parameters(name.clone(), None, None))
+            })
+            .clone();
+        counter.add(value, &tag_attributes(meter, tags));
+    }
+
+    pub(crate) fn gauge_set(
+        &self,
+        meter: &metrics::TemporalMeter,
+        name: String,
+        value: f64,
+        tags: HashMap<String, String>,
+    ) {
+        let gauge = self
+            .gauges
+            .lock()
+            .unwrap()
+            .entry(name)
+            .or_insert_with_key(|name| {
+                meter
+                    .inner
+                    .gauge_f64(build_metric_parameters(name.clone(), None, None))
+            })
+            .clone();
+        gauge.record(value, &tag_attributes(meter, tags));
+    }
+
+    pub(crate) fn histogram_record(
+        &self,
+        meter: &metrics::TemporalMeter,
+        name: String,
+        value: f64,
+        tags: HashMap<String, String>,
+    ) {
+        let histogram = self
+            .histograms
+            .lock()
+            .unwrap()
+            .entry(name)
+            .or_insert_with_key(|name| {
+                meter
+                    .inner
+                    .histogram_f64(build_metric_parameters(name.clone(), None, None))
+            })
+            .clone();
+        histogram.record(value, &tag_attributes(meter, tags));
+    }
+}
+
+/// The meter's default attributes extended with the given tags
+fn tag_attributes(
+    meter: &metrics::TemporalMeter,
+    tags: HashMap<String, String>,
+) -> metrics::MetricAttributes {
+    meter.inner.extend_attributes(
+        meter.inner.new_attributes(meter.default_attribs.clone()),
+        NewAttributes {
+            attributes: tags
+                .into_iter()
+                .map(|(k, v)| metrics::MetricKeyValue::new(k, metrics::MetricValue::String(v)))
+                .collect(),
+        },
+    )
+}
+
 fn build_metric_parameters(
     name: String,
     description: Option<String>,
@@ -303,6 +394,11 @@ pub struct BufferedMetric {
     pub kind: u8, // 0 - counter, 1 - gauge, 2 - histogram
 }
 
+// WARNING: These must match temporalio.runtime.BUFFERED_METRIC_KIND_* values
+pub const BUFFERED_METRIC_KIND_COUNTER: u8 = 0;
+pub const BUFFERED_METRIC_KIND_GAUGE: u8 = 1;
+pub const BUFFERED_METRIC_KIND_HISTOGRAM: u8 = 2;
+
 #[derive(Debug)]
 struct BufferedMetricAttributes(Py<PyDict>);
 
@@ -362,11 +458,15 @@ fn convert_metric_event(
                             Some(params.unit.to_string())
                         },
                         kind: match kind {
-                            metrics::MetricKind::Counter => 0,
-                            metrics::MetricKind::Gauge | metrics::MetricKind::GaugeF64 => 1,
+                            metrics::MetricKind::Counter => BUFFERED_METRIC_KIND_COUNTER,
+                            metrics::MetricKind::Gauge | metrics::MetricKind::GaugeF64 => {
+                                BUFFERED_METRIC_KIND_GAUGE
+                            }
                             metrics::MetricKind::Histogram
                             | metrics::MetricKind::HistogramF64
-                            | metrics::MetricKind::HistogramDuration => 2,
+                            | metrics::MetricKind::HistogramDuration => {
+                                BUFFERED_METRIC_KIND_HISTOGRAM
+                            }
                         },
                     },
                 )
@@ -1,13 +1,14 @@
 use futures::channel::mpsc::Receiver;
 use pyo3::exceptions::{PyRuntimeError, PyValueError};
 use pyo3::prelude::*;
+use pyo3::types::PyDict;
 use pythonize::pythonize;
 use std::collections::HashMap;
 use std::future::Future;
 use std::net::SocketAddr;
 use std::pin::Pin;
 use std::str::FromStr;
-use std::sync::Arc;
+use std::sync::{Arc, Mutex};
 use std::time::{Duration, UNIX_EPOCH};
 use temporal_sdk_core::telemetry::{
     build_otlp_metric_exporter, start_prometheus_metric_exporter, CoreLogStreamConsumer,
@@ -21,23 +22,45 @@ use temporal_sdk_core_api::telemetry::{
 };
 use tokio::task::JoinHandle;
 use tokio_stream::StreamExt;
-use tracing::Level;
+use tracing::{warn, Level};
 use url::Url;
 
-use crate::metric::{convert_metric_events, BufferedMetricRef, BufferedMetricUpdate};
+use crate::metric::{
+    convert_metric_events, BufferedMetricRef, BufferedMetricUpdate, BufferedMetricUpdateValue,
+    NamedMetrics, BUFFERED_METRIC_KIND_COUNTER,
+};
 
 #[pyclass]
 pub struct RuntimeRef {
     pub(crate) runtime: Runtime,
+    named_metrics: NamedMetrics,
 }
 
 #[derive(Clone)]
 pub(crate) struct Runtime {
     pub(crate) core: Arc<CoreRuntime>,
-    metrics_call_buffer: Option<Arc<MetricsCallBuffer<BufferedMetricRef>>>,
+    metric_buffer: Option<Arc<MetricBuffer>>,
     log_forwarder_handle: Option<Arc<JoinHandle<()>>>,
 }
 
+/// Core's metric call buffer, drained by the bridge both when Python retrieves
+/// updates and when a worker's snapshot is read, so snapshots are current even
+/// if Python rarely retrieves updates
+struct MetricBuffer {
+    calls: Arc<MetricsCallBuffer<BufferedMetricRef>>,
+    durations_as_seconds: bool,
+    /// Same bound as core's buffer
+    capacity: usize,
+    /// Drained from core but not yet retrieved by Python
+    pending: Mutex<Vec<BufferedMetricUpdate>>,
+    /// Latest values keyed by namespace and task queue, then by metric name
+    /// with its remaining attributes. Core's metric attributes do not identify
+    /// a worker, so workers polling the same task queue share a snapshot.
+    snapshots: Mutex<HashMap<(String, String), HashMap<String, BufferedMetricUpdateValue>>>,
+    /// Number of live workers sharing each snapshot
+    snapshot_workers: Mutex<HashMap<(String, String), usize>>,
+}
+
 #[derive(FromPyObject)]
 pub struct TelemetryConfig {
     logging: Option<LoggingConfig>,
@@ -48,6 +71,8 @@ pub struct TelemetryConfig {
 pub struct LoggingConfig {
     filter: String,
     forward_to: Option<PyObject>,
+    forward_max_batch_size: Option<usize>,
+    forward_max_batch_interval_millis: Option<u64>,
 }
 
 #[pyclass]
@@ -61,6 +86,7 @@ pub struct MetricsConfig {
     opentelemetry: Option<OpenTelemetryConfig>,
     prometheus: Option<PrometheusConfig>,
     buffered_with_size: usize,
+    buffered_durations_as_seconds: bool,
 
     attach_service_name: bool,
     global_tags: Option<HashMap<String, String>>,
@@ -85,20 +111,49 @@ pub struct PrometheusConfig {
     durations_as_seconds: bool,
 }
 
+/// Version of the sdk-core crate compiled into the bridge
+pub(crate) const CORE_VERSION: &str = env!("TEMPORAL_SDK_CORE_VERSION");
+
+/// Core version plus the sdk-core git commit, which is empty if it was not
+/// known at build time, and the bridge crate version
+pub(crate) fn core_build_info() -> HashMap<&'static str, &'static str> {
+    HashMap::from([
+        ("version", CORE_VERSION),
+        ("git_sha", env!("TEMPORAL_SDK_CORE_GIT_SHA")),
+        ("bridge_version", env!("CARGO_PKG_VERSION")),
+    ])
+}
+
 const FORWARD_LOG_BUFFER_SIZE: usize = 2048;
 const FORWARD_LOG_MAX_FREQ_MS: u64 = 10;
 
-pub fn init_runtime(telemetry_config: TelemetryConfig) -> PyResult<RuntimeRef> {
+pub fn init_runtime(
+    telemetry_config: TelemetryConfig,
+    worker_threads: Option<usize>,
+) -> PyResult<RuntimeRef> {
     // Have to build/start telemetry config pieces
     let mut telemetry_build = TelemetryOptionsBuilder::default();
 
     // Build logging config, capturing forwarding info to start later
-    let mut log_forwarding: Option<(Receiver<CoreLog>, PyObject)> = None;
+    let mut log_forwarding: Option<(Receiver<CoreLog>, PyObject, usize, Duration)> = None;
     if let Some(logging_conf) = telemetry_config.logging {
         telemetry_build.logging(if let Some(forward_to) = logging_conf.forward_to {
+            let max_batch_size = logging_conf
+                .forward_max_batch_size
+                .unwrap_or(FORWARD_LOG_BUFFER_SIZE);
+            if max_batch_size == 0 {
+                return Err(PyValueError::new_err(
+                    "Log forwarding max batch size must be at least 1",
+                ));
+            }
+            let max_batch_interval = Duration::from_millis(
+                logging_conf
+                    .forward_max_batch_interval_millis
+                    .unwrap_or(FORWARD_LOG_MAX_FREQ_MS),
+            );
             // Note, actual log forwarding is started later
             let (consumer, stream) = CoreLogStreamConsumer::new(FORWARD_LOG_BUFFER_SIZE);
-            log_forwarding = Some((stream, forward_to));
+            log_forwarding = Some((stream, forward_to, max_batch_size, max_batch_interval));
             Logger::Push {
                 filter: logging_conf.filter.to_string(),
                 consumer: Arc::new(consumer),
@@ -119,18 +174,29 @@ pub fn init_runtime(telemetry_config: TelemetryConfig) -> PyResult<RuntimeRef> {
         }
     }
 
+    // Tokio defaults to one worker thread per CPU
+    let mut tokio_build = TokioRuntimeBuilder::default();
+    if let Some(worker_threads) = worker_threads {
+        if worker_threads == 0 {
+            return Err(PyValueError::new_err(
+                "Runtime worker threads must be at least 1",
+            ));
+        }
+        tokio_build.inner.worker_threads(worker_threads);
+    }
+
     // Create core runtime which starts tokio multi-thread runtime
     let mut core = CoreRuntime::new(
         telemetry_build
             .build()
             .map_err(|err| PyValueError::new_err(format!("Invalid telemetry config: {}", err)))?,
-        TokioRuntimeBuilder::default(),
+        tokio_build,
     )
     .map_err(|err| PyRuntimeError::new_err(format!("Failed initializing telemetry: {}", err)))?;
 
     // We late-bind the metrics after core runtime is created since it needs
     // the Tokio handle
-    let mut metrics_call_buffer: Option<Arc<MetricsCallBuffer<BufferedMetricRef>>> = None;
+    let mut metric_buffer: Option<Arc<MetricBuffer>> = None;
     if let Some(metrics_conf) = telemetry_config.metrics {
         let _guard = core.tokio_handle().enter();
         // If they want buffered, cannot have Prom/OTel and we make buffered
@@ -143,7 +209,14 @@ pub fn init_runtime(telemetry_config: TelemetryConfig) -> PyResult<RuntimeRef> {
             let buffer = Arc::new(MetricsCallBuffer::new(metrics_conf.buffered_with_size));
             core.telemetry_mut()
                 .attach_late_init_metrics(buffer.clone());
-            metrics_call_buffer = Some(buffer);
+            metric_buffer = Some(Arc::new(MetricBuffer {
+                calls: buffer,
+                durations_as_seconds: metrics_conf.buffered_durations_as_seconds,
+                capacity: metrics_conf.buffered_with_size,
+                pending: Default::default(),
+                snapshots: Default::default(),
+                snapshot_workers: Default::default(),
+            }));
         } else {
             core.telemetry_mut()
                 .attach_late_init_metrics(metrics_conf.try_into()?);
@@ -151,31 +224,31 @@ pub fn init_runtime(telemetry_config: TelemetryConfig) -> PyResult<RuntimeRef> {
     }
 
     // Start log forwarding if needed
-    let log_forwarder_handle = log_forwarding.map(|(stream, callback)| {
-        Arc::new(core.tokio_handle().spawn(async move {
-            let mut stream = std::pin::pin!(stream.chunks_timeout(
-                FORWARD_LOG_BUFFER_SIZE,
-                Duration::from_millis(FORWARD_LOG_MAX_FREQ_MS)
-            ));
-            while let Some(core_logs) = stream.next().await {
-                // Create vec of buffered logs
-                let entries = core_logs
-                    .into_iter()
-                    .map(|core_log| BufferedLogEntry { core_log })
-                    .collect::<Vec<_>>();
-                // We silently swallow errors here because logging them could
-                // cause a bad loop and we don't want to assume console presence
-                let _ = Python::with_gil(|py| callback.call1(py, (entries,)));
-            }
-        }))
-    });
+    let log_forwarder_handle =
+        log_forwarding.map(|(stream, callback, max_batch_size, max_batch_interval)| {
+            Arc::new(core.tokio_handle().spawn(async move {
+                let mut stream =
+                    std::pin::pin!(stream.chunks_timeout(max_batch_size, max_batch_interval));
+                while let Some(core_logs) = stream.next().await {
+                    // Create vec of buffered logs
+                    let entries = core_logs
+                        .into_iter()
+                        .map(|core_log| BufferedLogEntry { core_log })
+                        .collect::<Vec<_>>();
+                    // We silently swallow errors here because logging them could
+                    // cause a bad loop and we don't want to assume console presence
+                    let _ = Python::with_gil(|py| callback.call1(py, (entries,)));
+                }
+            }))
+        });
 
     Ok(RuntimeRef {
         runtime: Runtime {
             core: Arc::new(core),
-            metrics_call_buffer,
+            metric_buffer,
             log_forwarder_handle,
         },
+        named_metrics: Default::default(),
     })
 }
 
@@ -192,6 +265,126 @@ impl Runtime {
         let _guard = self.core.tokio_handle().enter();
         pyo3_asyncio::generic::future_into_py::<TokioRuntime, _, T>(py, fut)
     }
+
+    /// Dict of the latest buffered metric values for the workers with the
+    /// given namespace and task queue. Counters are totals across all of those
+    /// workers. Always empty when metrics are not buffered.
+    pub(crate) fn metric_snapshot<'p>(
+        &self,
+        py: Python<'p>,
+        namespace: &str,
+        task_queue: &str,
+    ) -> PyResult<&'p PyDict> {
+        let dict = PyDict::new(py);
+        let Some(buffer) = self.metric_buffer.as_ref() else {
+            return Ok(dict);
+        };
+        buffer.drain(py);
+        let snapshots = buffer.snapshots.lock().unwrap();
+        let key = (namespace.to_string(), task_queue.to_string());
+        if let Some(values) = snapshots.get(&key) {
+            for (key, value) in values {
+                dict.set_item(key, value.clone().into_py(py))?;
+            }
+        }
+        Ok(dict)
+    }
+
+    /// Records that a worker shares the snapshot for its namespace and task
+    /// queue, so the snapshot is kept until every such worker has shut down
+    pub(crate) fn register_metric_snapshot(&self, namespace: &str, task_queue: &str) {
+        if let Some(buffer) = self.metric_buffer.as_ref() {
+            let key = (namespace.to_string(), task_queue.to_string());
+            *buffer
+                .snapshot_workers
+                .lock()
+                .unwrap()
+                .entry(key)
+                .or_default() += 1;
+        }
+    }
+
+    /// Releases a shut down worker's share of its snapshot, forgetting the
+    /// snapshot once no other worker on the same task queue remains
+    pub(crate) fn clear_metric_snapshot(&self, namespace: &str, task_queue: &str) {
+        if let Some(buffer) = self.metric_buffer.as_ref() {
+            let key = (namespace.to_string(), task_queue.to_string());
+            let mut snapshot_workers = buffer.snapshot_workers.lock().unwrap();
+            if let Some(workers) = snapshot_workers.get_mut(&key) {
+                *workers -= 1;
+                if *workers > 0 {
+                    return;
+                }
+                snapshot_workers.remove(&key);
+            }
+            buffer.snapshots.lock().unwrap().remove(&key);
+        }
+    }
+}
+
+impl MetricBuffer {
+    /// Moves everything in core's buffer to the pending updates, recording it
+    /// in the snapshots on the way
+    fn drain(&self, py: Python) {
+        let updates = convert_metric_events(py, self.calls.retrieve(), self.durations_as_seconds);
+        self.record_snapshots(py, &updates);
+        let mut pending = self.pending.lock().unwrap();
+        let room = self.capacity.saturating_sub(pending.len());
+        if updates.len() > room {
+            warn!(
+                "Metric buffer full, dropping {} metric updates",
+                updates.len() - room
+            );
+        }
+        pending.extend(updates.into_iter().take(room));
+    }
+
+    fn retrieve(&self, py: Python) -> Vec<BufferedMetricUpdate> {
+        self.drain(py);
+        std::mem::take(&mut *self.pending.lock().unwrap())
+    }
+
+    fn record_snapshots(&self, py: Python, updates: &[BufferedMetricUpdate]) {
+        use BufferedMetricUpdateValue::{F64, U64};
+        let mut snapshots = self.snapshots.lock().unwrap();
+        for update in updates {
+            let attrs = update.attributes.as_ref(py);
+            let attr = |name: &str| {
+                attrs
+                    .get_item(name)
+                    .ok()
+                    .flatten()
+                    .and_then(|v| v.extract::<String>().ok())
+            };
+            let (Some(namespace), Some(task_queue)) = (attr("namespace"), attr("task_queue"))
+            else {
+                continue;
+            };
+            let metric = update.metric.borrow(py);
+            let mut labels = attrs
+                .iter()
+                .filter_map(|(k, v)| {
+                    let k = k.extract::<String>().ok()?;
+                    (k != "task_queue" && k != "namespace").then(|| format!("{}=\"{}\"", k, v))
+                })
+                .collect::<Vec<_>>();
+            labels.sort();
+            let key = if labels.is_empty() {
+                metric.name.clone()
+            } else {
+                format!("{}{{{}}}", metric.name, labels.join(","))
+            };
+            let values = snapshots.entry((namespace, task_queue)).or_default();
+            // Counters are deltas so they accumulate, everything else is the
+            // latest recorded value
+            let value = match (metric.kind, values.get(&key), &update.value) {
+                (BUFFERED_METRIC_KIND_COUNTER, Some(U64(total)), U64(delta)) => U64(total + delta),
+                (BUFFERED_METRIC_KIND_COUNTER, Some(F64(total)), F64(delta)) => F64(total + delta),
+                _ => update.value.clone(),
+            };
+            values.insert(key, value);
+        }
+    }
 }
 
 impl Drop for Runtime {
@@ -205,20 +398,34 @@ impl Drop for Runtime {
 
 #[pymethods]
 impl RuntimeRef {
-    fn retrieve_buffered_metrics(
-        &self,
-        py: Python,
-        durations_as_seconds: bool,
-    ) -> Vec<BufferedMetricUpdate> {
-        convert_metric_events(
-            py,
-            self.runtime
-                .metrics_call_buffer
-                .as_ref()
-                .expect("Attempting to retrieve buffered metrics without buffer")
-                .retrieve(),
-            durations_as_seconds,
-        )
+    fn retrieve_buffered_metrics(&self, py: Python) -> Vec<BufferedMetricUpdate> {
+        self.runtime
+            .metric_buffer
+            .as_ref()
+            .expect("Attempting to retrieve buffered metrics without buffer")
+            .retrieve(py)
+    }
+
+    /// Adds to the named counter, doing nothing if metrics are not enabled
+    fn counter_add(&self, name: String, value: u64, tags: HashMap<String, String>) {
+        if let Some(meter) = self.runtime.core.telemetry().get_metric_meter() {
+            self.named_metrics.counter_add(&meter, name, value, tags);
+        }
+    }
+
+    /// Sets the named gauge, doing nothing if metrics are not enabled
+    fn gauge_set(&self, name: String, value: f64, tags: HashMap<String, String>) {
+        if let Some(meter) = self.runtime.core.telemetry().get_metric_meter() {
+            self.named_metrics.gauge_set(&meter, name, value, tags);
+        }
+    }
+
+    /// Records to the named histogram, doing nothing if metrics are not enabled
+    fn histogram_record(&self, name: String, value: f64, tags: HashMap<String, String>) {
+        if let Some(meter) = self.runtime.core.telemetry().get_metric_meter() {
+            self.named_metrics
+                .histogram_record(&meter, name, value, tags);
+        }
     }
 
     fn write_test_info_log(&self, message: &str, extra_data: &str) {
@@ -1,43 +1,180 @@
 #![allow(non_local_definitions)] // pymethods annotations causing issues with this lint
 
 use anyhow::Context;
+use futures::future::{BoxFuture, Either};
 use log::error;
 use prost::Message;
-use pyo3::exceptions::{PyException, PyRuntimeError, PyValueError};
+use pyo3::exceptions::{PyException, PyRuntimeError, PyStopAsyncIteration, PyValueError};
 use pyo3::prelude::*;
-use pyo3::types::{PyBytes, PyTuple};
+use pyo3::types::{PyBytes, PyDict, PyTuple};
 use std::collections::HashMap;
 use std::collections::HashSet;
+use std::collections::VecDeque;
+use std::future::Future;
 use std::marker::PhantomData;
-use std::sync::{Arc, OnceLock};
+use std::sync::atomic::{AtomicBool, AtomicU64, AtomicU8, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex, OnceLock};
 use std::time::Duration;
+use temporal_client::WorkflowService;
 use temporal_sdk_core::api::errors::PollError;
 use temporal_sdk_core::replay::{HistoryForReplay, ReplayWorkerInput};
 use temporal_sdk_core_api::errors::WorkflowErrorType;
 use temporal_sdk_core_api::worker::{
-    SlotInfo, SlotInfoTrait, SlotKind, SlotKindType, SlotMarkUsedContext, SlotReleaseContext,
-    SlotReservationContext, SlotSupplier as SlotSupplierTrait, SlotSupplierPermit,
+    ActivitySlotKind, LocalActivitySlotKind, NexusSlotKind, SlotInfo, SlotInfoTrait, SlotKind,
+    SlotKindType, SlotMarkUsedContext, SlotReleaseContext, SlotReservationContext,
+    SlotSupplier as SlotSupplierTrait, SlotSupplierPermit, WorkerTuner, WorkerVersioningStrategy,
+    WorkflowSlotKind,
 };
 use temporal_sdk_core_api::Worker;
-use temporal_sdk_core_protos::coresdk::workflow_completion::WorkflowActivationCompletion;
+use temporal_sdk_core_protos::coresdk::activity_result::{
+    self, activity_execution_result, ActivityExecutionResult,
+};
+use temporal_sdk_core_protos::coresdk::activity_task::{activity_task, ActivityTask};
+use temporal_sdk_core_protos::coresdk::workflow_activation::{
+    remove_from_cache::EvictionReason, workflow_activation_job, WorkflowActivation,
+};
+use temporal_sdk_core_protos::coresdk::workflow_commands::WorkflowCommand;
+use temporal_sdk_core_protos::coresdk::workflow_completion::{
+    workflow_activation_completion, Success, WorkflowActivationCompletion,
+};
 use temporal_sdk_core_protos::coresdk::{ActivityHeartbeat, ActivityTaskCompletion};
-use temporal_sdk_core_protos::temporal::api::history::v1::History;
-use tokio::sync::mpsc::{channel, Sender};
+use temporal_sdk_core_protos::temporal::api::common::v1::{Payloads, WorkflowExecution};
+use temporal_sdk_core_protos::temporal::api::enums::v1::{
+    TaskQueueKind, TaskQueueType, VersioningBehavior,
+};
+use temporal_sdk_core_protos::temporal::api::failure::v1::{
+    failure::FailureInfo, ApplicationFailureInfo, CanceledFailureInfo, Failure,
+};
+use temporal_sdk_core_protos::temporal::api::history::v1::{history_event, History};
+use temporal_sdk_core_protos::temporal::api::taskqueue::v1::TaskQueue;
+use temporal_sdk_core_protos::temporal::api::workflowservice::v1::{
+    DescribeNamespaceRequest, DescribeTaskQueueRequest, GetSystemInfoRequest,
+    GetWorkflowExecutionHistoryRequest,
+};
+use tokio::sync::mpsc::error::TrySendError;
+use tokio::sync::mpsc::{
+    channel, unbounded_channel, Receiver, Sender, UnboundedReceiver, UnboundedSender,
+};
+use tokio::sync::Notify;
 use tokio_stream::wrappers::ReceiverStream;
 
 use crate::client;
 use crate::runtime;
 
 pyo3::create_exception!(temporal_sdk_bridge, PollShutdownError, PyException);
+// Both subclass RuntimeError, which is what poll failures were raised as before
+// these existed
+pyo3::create_exception!(temporal_sdk_bridge, PollTransportError, PyRuntimeError);
+pyo3::create_exception!(temporal_sdk_bridge, PollInternalError, PyRuntimeError);
+pyo3::create_exception!(temporal_sdk_bridge, CompletionError, PyRuntimeError);
+// Subclasses ValueError, which is what decode failures were raised as before
+// this existed
+pyo3::create_exception!(temporal_sdk_bridge, InvalidProtoError, PyValueError);
 
 #[pyclass]
 pub struct WorkerRef {
     worker: Option<Arc<temporal_sdk_core::Worker>>,
+    /// Notified whenever a `WorkerHandle` is dropped
+    worker_released: Arc<Notify>,
     /// Set upon the call to `validate`, with the task locals for the event loop at that time, which
     /// is whatever event loop the user is running their worker in. This loop might be needed by
     /// other rust-created threads that want to run async python code.
     event_loop_task_locals: Arc<OnceLock<pyo3_asyncio::TaskLocals>>,
+    /// Run IDs of workflows that have been given activations and not yet
+    /// been given an eviction
+    cached_run_ids: Arc<CachedRuns>,
+    activation_timings: Arc<ActivationTimings>,
+    /// Only set for replay workers
+    replay_results: Option<Arc<ReplayResultTracker>>,
+    /// Only set for replay workers
+    replay_channels: Option<ReplayChannels>,
+    outstanding_polls: Arc<OutstandingPolls>,
+    poll_stats: Arc<PollStats>,
+    parked_polls: Arc<tokio::sync::Mutex<ParkedPolls>>,
+    /// Called with the encoded bytes of every workflow activation completion,
+    /// returning the bytes to actually decode and send to core
+    completion_interceptor: Option<PyObject>,
+    /// Only set if slot events were enabled in the config
+    slot_events: Option<SlotEventReceiver>,
+    /// Description of the tuner as given to core, see `TunerHolder::describe`
+    tuner_config: serde_json::Value,
+    /// Description of the poller behaviors as given to core, see
+    /// `WorkerConfig::describe_poller_behaviors`
+    poller_behaviors: serde_json::Value,
+    /// Client the worker was created or last replaced with, unset for replay
+    /// workers
+    client: Mutex<Option<client::Client>>,
     runtime: runtime::Runtime,
+    #[pyo3(get)]
+    namespace: String,
+    #[pyo3(get)]
+    task_queue: String,
+    /// Build ID of the deployment version if deployment options are set,
+    /// otherwise the configured build ID
+    #[pyo3(get)]
+    build_id: String,
+}
+
+/// Details gathered while validating a worker
+#[pyclass]
+pub struct WorkerValidation {
+    /// Validation failure message, if validation failed
+    #[pyo3(get)]
+    error: Option<String>,
+    /// Whether the worker's namespace could be described on the server
+    #[pyo3(get)]
+    namespace_exists: bool,
+    /// Server version reported by the server, if it could be retrieved
+    #[pyo3(get)]
+    server_version: Option<String>,
+}
+
+#[pymethods]
+impl WorkerValidation {
+    #[getter]
+    fn valid(&self) -> bool {
+        self.error.is_none()
+    }
+}
+
+/// Allows Python to stop an in-flight cancellable poll without shutting the
+/// worker down. Once cancelled, it stays cancelled until reset.
+#[pyclass]
+#[derive(Clone, Default)]
+pub struct PollCanceller {
+    cancelled: Arc<AtomicBool>,
+    notify: Arc<Notify>,
+}
+
+impl PollCanceller {
+    async fn cancelled(&self) {
+        loop {
+            // Must create the notified future before checking the flag so a
+            // cancel in between is not missed
+            let notified = self.notify.notified();
+            if self.cancelled.load(Ordering::SeqCst) {
+                return;
+            }
+            notified.await;
+        }
+    }
+}
+
+#[pymethods]
+impl PollCanceller {
+    fn cancel(&self) {
+        self.cancelled.store(true, Ordering::SeqCst);
+        self.notify.notify_waiters();
+    }
+
+    fn reset(&self) {
+        self.cancelled.store(false, Ordering::SeqCst);
+    }
+
+    #[getter]
+    fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::SeqCst)
+    }
 }
 
 #[derive(FromPyObject)]
@@ -47,20 +184,158 @@ pub struct WorkerConfig {
     build_id: String,
     identity_override: Option<String>,
     max_cached_workflows: usize,
+    disable_sticky_queues: bool,
     tuner: TunerHolder,
-    max_concurrent_workflow_task_polls: usize,
+    workflow_task_poller_behavior: PollerBehavior,
+    /// Must be within [0.0, 1.0]. Ignored entirely when sticky queues are
+    /// disabled since all polls are then nonsticky.
     nonsticky_to_sticky_poll_ratio: f32,
-    max_concurrent_activity_task_polls: usize,
+    activity_task_poller_behavior: PollerBehavior,
     no_remote_activities: bool,
     sticky_queue_schedule_to_start_timeout_millis: u64,
     max_heartbeat_throttle_interval_millis: u64,
     default_heartbeat_throttle_interval_millis: u64,
+    local_timeout_buffer_for_activities_millis: u64,
     max_activities_per_second: Option<f64>,
     max_task_queue_activities_per_second: Option<f64>,
-    graceful_shutdown_period_millis: u64,
+    /// Core has no local activity rate limit, so this is enforced by the
+    /// bridge when reserving local activity slots. Unbounded when unset.
+    max_local_activities_per_second: Option<f64>,
+    graceful_shutdown_period_millis: Option<u64>,
     use_worker_versioning: bool,
-    nondeterminism_as_workflow_fail: bool,
-    nondeterminism_as_workflow_fail_for_types: HashSet<String>,
+    deployment_options: Option<WorkerDeploymentOptions>,
+    workflow_failure_errors: HashSet<String>,
+    workflow_types_to_failure_errors: HashMap<String, HashSet<String>>,
+    completion_interceptor: Option<PyObject>,
+    emit_slot_events: bool,
+    /// Track the time of the last successful poll for
+    /// `WorkerRef::seconds_since_last_poll`
+    poll_watchdog: bool,
+}
+
+impl WorkerConfig {
+    /// Namespace, task queue and effective build ID, kept on the worker so they
+    /// can be read after the config has been converted
+    fn identifiers(&self) -> (String, String, String) {
+        let build_id = match &self.deployment_options {
+            Some(opts) => opts.build_id.clone(),
+            None => self.build_id.clone(),
+        };
+        (self.namespace.clone(), self.task_queue.clone(), build_id)
+    }
+
+    /// The tuner's description plus the local activity rate limit, which the
+    /// bridge enforces around whatever local activity slot supplier is given
+    fn describe_tuner(&self) -> serde_json::Value {
+        let mut description = self.tuner.describe();
+        description["local_activity"]["max_per_second"] =
+            serde_json::json!(self.max_local_activities_per_second);
+        description
+    }
+
+    /// Poller behaviors as given to core, with no activity entry if the worker
+    /// never polls for activities
+    fn describe_poller_behaviors(&self) -> serde_json::Value {
+        serde_json::json!({
+            "workflow": self.workflow_task_poller_behavior.describe(),
+            "activity": (!self.no_remote_activities)
+                .then(|| self.activity_task_poller_behavior.describe()),
+        })
+    }
+}
+
+#[derive(FromPyObject)]
+pub struct PollerBehaviorSimpleMaximum {
+    maximum: usize,
+}
+
+#[derive(FromPyObject)]
+pub struct PollerBehaviorAutoscaling {
+    minimum: usize,
+    maximum: usize,
+    initial: usize,
+}
+
+/// Recreates [temporal_sdk_core_api::worker::PollerBehavior]
+#[derive(FromPyObject)]
+pub enum PollerBehavior {
+    // Autoscaling also has a maximum, so it must be tried first
+    Autoscaling(PollerBehaviorAutoscaling),
+    SimpleMaximum(PollerBehaviorSimpleMaximum),
+}
+
+impl PollerBehavior {
+    fn describe(&self) -> serde_json::Value {
+        match self {
+            PollerBehavior::SimpleMaximum(simple) => serde_json::json!({
+                "kind": "simple_maximum",
+                "maximum": simple.maximum,
+            }),
+            PollerBehavior::Autoscaling(auto) => serde_json::json!({
+                "kind": "autoscaling",
+                "minimum": auto.minimum,
+                "maximum": auto.maximum,
+                "initial": auto.initial,
+            }),
+        }
+    }
+}
+
+impl TryFrom<PollerBehavior> for temporal_sdk_core_api::worker::PollerBehavior {
+    type Error = PyErr;
+
+    fn try_from(behavior: PollerBehavior) -> PyResult<Self> {
+        match behavior {
+            PollerBehavior::SimpleMaximum(simple) => {
+                Ok(temporal_sdk_core_api::worker::PollerBehavior::SimpleMaximum(simple.maximum))
+            }
+            PollerBehavior::Autoscaling(auto) => {
+                if auto.minimum > auto.initial || auto.initial > auto.maximum {
+                    return Err(PyValueError::new_err(format!(
+                        "Invalid autoscaling poller behavior, expected minimum ({}) <= initial ({}) <= maximum ({})",
+                        auto.minimum, auto.initial, auto.maximum
+                    )));
+                }
+                Ok(temporal_sdk_core_api::worker::PollerBehavior::Autoscaling {
+                    minimum: auto.minimum,
+                    maximum: auto.maximum,
+                    initial: auto.initial,
+                })
+            }
+        }
+    }
+}
+
+#[derive(FromPyObject)]
+pub struct WorkerDeploymentOptions {
+    deployment_name: String,
+    build_id: String,
+    default_versioning_behavior: i32,
+}
+
+impl TryFrom<WorkerDeploymentOptions> for temporal_sdk_core_api::worker::WorkerDeploymentOptions {
+    type Error = PyErr;
+
+    fn try_from(opts: WorkerDeploymentOptions) -> PyResult<Self> {
+        let behavior =
+            VersioningBehavior::try_from(opts.default_versioning_behavior).map_err(|_| {
+                PyValueError::new_err(format!(
+                    "Invalid default versioning behavior: {}",
+                    opts.default_versioning_behavior
+                ))
+            })?;
+        Ok(temporal_sdk_core_api::worker::WorkerDeploymentOptions {
+            version: temporal_sdk_core_api::worker::WorkerDeploymentVersion {
+                deployment_name: opts.deployment_name,
+                build_id: opts.build_id,
+            },
+            use_worker_versioning: true,
+            default_versioning_behavior: match behavior {
+                VersioningBehavior::Unspecified => None,
+                behavior => Some(behavior),
+            },
+        })
+    }
 }
 
 #[derive(FromPyObject)]
@@ -70,11 +345,55 @@ pub struct TunerHolder {
     local_activity_slot_supplier: SlotSupplier,
 }
 
+impl TunerHolder {
+    /// Each slot type's supplier kind and parameters as core will use them.
+    /// Resource-based targets come from the single shared controller, which
+    /// conversion validates all resource-based suppliers agree on.
+    fn describe(&self) -> serde_json::Value {
+        serde_json::json!({
+            "workflow": self.workflow_slot_supplier.describe(),
+            "activity": self.activity_slot_supplier.describe(),
+            "local_activity": self.local_activity_slot_supplier.describe(),
+        })
+    }
+}
+
 #[derive(FromPyObject)]
 pub enum SlotSupplier {
     FixedSize(FixedSizeSlotSupplier),
     ResourceBased(ResourceBasedSlotSupplier),
     Custom(CustomSlotSupplier),
+    Manual(ManualSlotSupplier),
+}
+
+impl SlotSupplier {
+    fn describe(&self) -> serde_json::Value {
+        match self {
+            SlotSupplier::FixedSize(ss) => serde_json::json!({
+                "kind": "fixed_size",
+                "num_slots": ss.num_slots,
+            }),
+            SlotSupplier::ResourceBased(ss) => serde_json::json!({
+                "kind": "resource_based",
+                "minimum_slots": ss.minimum_slots,
+                "maximum_slots": ss.maximum_slots,
+                "ramp_throttle_millis": ss.ramp_throttle_ms,
+                "target_memory_usage": ss.tuner_config.target_memory_usage,
+                "target_cpu_usage": ss.tuner_config.target_cpu_usage,
+                // Unset gains and thresholds are left to core's defaults
+                "memory_p_gain": ss.tuner_config.memory_p_gain,
+                "memory_i_gain": ss.tuner_config.memory_i_gain,
+                "memory_d_gain": ss.tuner_config.memory_d_gain,
+                "memory_output_threshold": ss.tuner_config.memory_output_threshold,
+                "cpu_p_gain": ss.tuner_config.cpu_p_gain,
+                "cpu_i_gain": ss.tuner_config.cpu_i_gain,
+                "cpu_d_gain": ss.tuner_config.cpu_d_gain,
+                "cpu_output_threshold": ss.tuner_config.cpu_output_threshold,
+            }),
+            SlotSupplier::Custom(_) => serde_json::json!({ "kind": "custom" }),
+            SlotSupplier::Manual(_) => serde_json::json!({ "kind": "manual" }),
+        }
+    }
 }
 
 #[derive(FromPyObject)]
@@ -103,6 +422,8 @@ pub struct SlotReserveCtx {
     pub worker_build_id: String,
     #[pyo3(get)]
     pub is_sticky: bool,
+    #[pyo3(get)]
+    pub num_issued_slots: usize,
 }
 
 impl SlotReserveCtx {
@@ -118,6 +439,7 @@ impl SlotReserveCtx {
             worker_identity: ctx.worker_identity().to_string(),
             worker_build_id: ctx.worker_build_id().to_string(),
             is_sticky: ctx.is_sticky(),
+            num_issued_slots: ctx.num_issued_slots(),
         }
     }
 }
@@ -369,293 +691,2824 @@ impl<SK: SlotKind + Send + Sync> SlotSupplierTrait for CustomSlotSupplierOfType<
     }
 }
 
-#[derive(FromPyObject, Clone, Copy, PartialEq)]
-pub struct ResourceBasedTunerConfig {
-    target_memory_usage: f64,
-    target_cpu_usage: f64,
+/// Slot supplier whose reservations succeed only while it is not blocked, for
+/// deterministically starving slots in tests
+#[pyclass]
+#[derive(Clone)]
+pub struct ManualSlotSupplier {
+    gate: Arc<ManualSlotGate>,
 }
 
-macro_rules! enter_sync {
-    ($runtime:expr) => {
-        if let Some(subscriber) = $runtime.core.telemetry().trace_subscriber() {
-            temporal_sdk_core::telemetry::set_trace_subscriber_for_current_thread(subscriber);
+struct ManualSlotGate {
+    blocked: AtomicBool,
+    changed: Notify,
+}
+
+#[pymethods]
+impl ManualSlotSupplier {
+    #[new]
+    #[pyo3(signature = (blocked=false))]
+    fn new(blocked: bool) -> Self {
+        ManualSlotSupplier {
+            gate: Arc::new(ManualSlotGate {
+                blocked: AtomicBool::new(blocked),
+                changed: Notify::new(),
+            }),
         }
-        let _guard = $runtime.core.tokio_handle().enter();
-    };
+    }
+
+    /// Lets pending and future reservations through
+    fn permit(&self) {
+        self.gate.blocked.store(false, Ordering::SeqCst);
+        self.gate.changed.notify_waiters();
+    }
+
+    /// Makes future reservations wait until `permit` is called. Slots already
+    /// issued are unaffected.
+    fn block(&self) {
+        self.gate.blocked.store(true, Ordering::SeqCst);
+    }
+
+    #[getter]
+    fn is_blocked(&self) -> bool {
+        self.gate.blocked.load(Ordering::SeqCst)
+    }
 }
 
-pub fn new_worker(
-    runtime_ref: &runtime::RuntimeRef,
-    client: &client::ClientRef,
-    config: WorkerConfig,
-) -> PyResult<WorkerRef> {
-    enter_sync!(runtime_ref.runtime);
-    let event_loop_task_locals = Arc::new(OnceLock::new());
-    let config = convert_worker_config(config, event_loop_task_locals.clone())?;
-    let worker = temporal_sdk_core::init_worker(
-        &runtime_ref.runtime.core,
-        config,
-        client.retry_client.clone().into_inner(),
-    )
-    .context("Failed creating worker")?;
-    Ok(WorkerRef {
-        worker: Some(Arc::new(worker)),
-        event_loop_task_locals,
-        runtime: runtime_ref.runtime.clone(),
-    })
+struct ManualSlotSupplierOfType<SK: SlotKind> {
+    gate: Arc<ManualSlotGate>,
+    _phantom: PhantomData<SK>,
 }
 
-pub fn new_replay_worker<'a>(
-    py: Python<'a>,
-    runtime_ref: &runtime::RuntimeRef,
-    config: WorkerConfig,
-) -> PyResult<&'a PyTuple> {
-    enter_sync!(runtime_ref.runtime);
-    let event_loop_task_locals = Arc::new(OnceLock::new());
-    let config = convert_worker_config(config, event_loop_task_locals.clone())?;
-    let (history_pusher, stream) = HistoryPusher::new(runtime_ref.runtime.clone());
-    let worker = WorkerRef {
-        worker: Some(Arc::new(
-            temporal_sdk_core::init_replay_worker(ReplayWorkerInput::new(config, stream)).map_err(
-                |err| PyValueError::new_err(format!("Failed creating replay worker: {}", err)),
-            )?,
-        )),
-        event_loop_task_locals: Default::default(),
-        runtime: runtime_ref.runtime.clone(),
-    };
-    Ok(PyTuple::new(
-        py,
-        [worker.into_py(py), history_pusher.into_py(py)],
-    ))
+#[async_trait::async_trait]
+impl<SK: SlotKind + Send + Sync> SlotSupplierTrait for ManualSlotSupplierOfType<SK> {
+    type SlotKind = SK;
+
+    async fn reserve_slot(&self, _ctx: &dyn SlotReservationContext) -> SlotSupplierPermit {
+        loop {
+            // Must create before checking so a change in between is not missed
+            let changed = self.gate.changed.notified();
+            if !self.gate.blocked.load(Ordering::SeqCst) {
+                return SlotSupplierPermit::default();
+            }
+            changed.await;
+        }
+    }
+
+    fn try_reserve_slot(&self, _ctx: &dyn SlotReservationContext) -> Option<SlotSupplierPermit> {
+        (!self.gate.blocked.load(Ordering::SeqCst)).then(SlotSupplierPermit::default)
+    }
+
+    fn mark_slot_used(&self, _ctx: &dyn SlotMarkUsedContext<SlotKind = Self::SlotKind>) {}
+
+    fn release_slot(&self, _ctx: &dyn SlotReleaseContext<SlotKind = Self::SlotKind>) {}
+
+    fn available_slots(&self) -> Option<usize> {
+        None
+    }
 }
 
-#[pymethods]
-impl WorkerRef {
-    fn validate<'p>(&self, py: Python<'p>) -> PyResult<&'p PyAny> {
-        let worker = self.worker.as_ref().unwrap().clone();
-        // Set custom slot supplier task locals so they can run futures.
-        // Event loop is assumed to be running at this point.
-        let task_locals = pyo3_asyncio::TaskLocals::with_running_loop(py)?.copy_context(py)?;
-        self.event_loop_task_locals
-            .set(task_locals)
-            .expect("must only be set once");
+/// Tuner whose local activity slot reservations are paced to a maximum rate,
+/// with every other supplier taken from the wrapped tuner as is
+struct LocalActivityRateLimitedTuner {
+    inner: Arc<dyn WorkerTuner + Send + Sync>,
+    local_activity: Arc<RateLimitedSlotSupplier<LocalActivitySlotKind>>,
+}
 
-        self.runtime.future_into_py(py, async move {
-            worker
-                .validate()
-                .await
-                .context("Worker validation failed")
-                .map_err(Into::into)
-        })
+impl LocalActivityRateLimitedTuner {
+    fn new(inner: Arc<dyn WorkerTuner + Send + Sync>, interval: Duration) -> Self {
+        LocalActivityRateLimitedTuner {
+            local_activity: Arc::new(RateLimitedSlotSupplier {
+                inner: inner.local_activity_slot_supplier(),
+                interval,
+                next_allowed: Mutex::new(tokio::time::Instant::now()),
+            }),
+            inner,
+        }
     }
+}
 
-    fn poll_workflow_activation<'p>(&self, py: Python<'p>) -> PyResult<&'p PyAny> {
-        let worker = self.worker.as_ref().unwrap().clone();
-        self.runtime.future_into_py(py, async move {
-            let bytes = match worker.poll_workflow_activation().await {
-                Ok(act) => act.encode_to_vec(),
-                Err(PollError::ShutDown) => return Err(PollShutdownError::new_err(())),
-                Err(err) => return Err(PyRuntimeError::new_err(format!("Poll failure: {}", err))),
-            };
-            let bytes: &[u8] = &bytes;
-            Ok(Python::with_gil(|py| bytes.into_py(py)))
-        })
+impl WorkerTuner for LocalActivityRateLimitedTuner {
+    fn workflow_task_slot_supplier(
+        &self,
+    ) -> Arc<dyn SlotSupplierTrait<SlotKind = WorkflowSlotKind> + Send + Sync> {
+        self.inner.workflow_task_slot_supplier()
     }
 
-    fn poll_activity_task<'p>(&self, py: Python<'p>) -> PyResult<&'p PyAny> {
-        let worker = self.worker.as_ref().unwrap().clone();
-        self.runtime.future_into_py(py, async move {
-            let bytes = match worker.poll_activity_task().await {
-                Ok(task) => task.encode_to_vec(),
-                Err(PollError::ShutDown) => return Err(PollShutdownError::new_err(())),
-                Err(err) => return Err(PyRuntimeError::new_err(format!("Poll failure: {}", err))),
-            };
-            let bytes: &[u8] = &bytes;
-            Ok(Python::with_gil(|py| bytes.into_py(py)))
-        })
+    fn activity_task_slot_supplier(
+        &self,
+    ) -> Arc<dyn SlotSupplierTrait<SlotKind = ActivitySlotKind> + Send + Sync> {
+        self.inner.activity_task_slot_supplier()
     }
 
-    fn complete_workflow_activation<'p>(
+    fn local_activity_slot_supplier(
         &self,
-        py: Python<'p>,
-        proto: &PyBytes,
-    ) -> PyResult<&'p PyAny> {
-        let worker = self.worker.as_ref().unwrap().clone();
-        let completion = WorkflowActivationCompletion::decode(proto.as_bytes())
-            .map_err(|err| PyValueError::new_err(format!("Invalid proto: {}", err)))?;
-        self.runtime.future_into_py(py, async move {
-            worker
-                .complete_workflow_activation(completion)
-                .await
-                .context("Completion failure")
-                .map_err(Into::into)
-        })
+    ) -> Arc<dyn SlotSupplierTrait<SlotKind = LocalActivitySlotKind> + Send + Sync> {
+        self.local_activity.clone()
     }
 
-    fn complete_activity_task<'p>(&self, py: Python<'p>, proto: &PyBytes) -> PyResult<&'p PyAny> {
-        let worker = self.worker.as_ref().unwrap().clone();
-        let completion = ActivityTaskCompletion::decode(proto.as_bytes())
-            .map_err(|err| PyValueError::new_err(format!("Invalid proto: {}", err)))?;
-        self.runtime.future_into_py(py, async move {
-            worker
-                .complete_activity_task(completion)
-                .await
-                .context("Completion failure")
-                .map_err(Into::into)
-        })
+    fn nexus_task_slot_supplier(
+        &self,
+    ) -> Arc<dyn SlotSupplierTrait<SlotKind = NexusSlotKind> + Send + Sync> {
+        self.inner.nexus_task_slot_supplier()
     }
+}
 
-    fn record_activity_heartbeat(&self, proto: &PyBytes) -> PyResult<()> {
-        enter_sync!(self.runtime);
-        let heartbeat = ActivityHeartbeat::decode(proto.as_bytes())
-            .map_err(|err| PyValueError::new_err(format!("Invalid proto: {}", err)))?;
-        self.worker
-            .as_ref()
-            .unwrap()
-            .record_activity_heartbeat(heartbeat);
-        Ok(())
+/// Spaces out reservations of the wrapped supplier by a fixed interval, a
+/// token bucket holding a single token
+struct RateLimitedSlotSupplier<SK: SlotKind> {
+    inner: Arc<dyn SlotSupplierTrait<SlotKind = SK> + Send + Sync>,
+    interval: Duration,
+    /// When the next reservation may happen
+    next_allowed: Mutex<tokio::time::Instant>,
+}
+
+#[async_trait::async_trait]
+impl<SK: SlotKind + Send + Sync> SlotSupplierTrait for RateLimitedSlotSupplier<SK> {
+    type SlotKind = SK;
+
+    async fn reserve_slot(&self, ctx: &dyn SlotReservationContext) -> SlotSupplierPermit {
+        // The turn is claimed up front so concurrent reservations queue up
+        // behind each other instead of all waking at the same instant
+        let turn = {
+            let mut next_allowed = self.next_allowed.lock().unwrap();
+            let turn = (*next_allowed).max(tokio::time::Instant::now());
+            *next_allowed = turn + self.interval;
+            turn
+        };
+        tokio::time::sleep_until(turn).await;
+        self.inner.reserve_slot(ctx).await
     }
 
-    fn request_workflow_eviction(&self, run_id: &str) -> PyResult<()> {
-        enter_sync!(self.runtime);
-        self.worker
-            .as_ref()
-            .unwrap()
-            .request_workflow_eviction(run_id);
-        Ok(())
+    fn try_reserve_slot(&self, ctx: &dyn SlotReservationContext) -> Option<SlotSupplierPermit> {
+        let mut next_allowed = self.next_allowed.lock().unwrap();
+        let now = tokio::time::Instant::now();
+        if *next_allowed > now {
+            return None;
+        }
+        let permit = self.inner.try_reserve_slot(ctx)?;
+        *next_allowed = now + self.interval;
+        Some(permit)
     }
 
-    fn replace_client(&self, client: &client::ClientRef) {
-        self.worker
-            .as_ref()
-            .expect("missing worker")
-            .replace_client(client.retry_client.clone().into_inner());
+    fn mark_slot_used(&self, ctx: &dyn SlotMarkUsedContext<SlotKind = Self::SlotKind>) {
+        self.inner.mark_slot_used(ctx);
     }
 
-    fn initiate_shutdown(&self) -> PyResult<()> {
-        let worker = self.worker.as_ref().unwrap().clone();
-        worker.initiate_shutdown();
-        Ok(())
+    fn release_slot(&self, ctx: &dyn SlotReleaseContext<SlotKind = Self::SlotKind>) {
+        self.inner.release_slot(ctx);
     }
 
-    fn finalize_shutdown<'p>(&mut self, py: Python<'p>) -> PyResult<&'p PyAny> {
-        // Take the worker out of the option and leave None. This should be the
-        // only reference remaining to the worker so try_unwrap will work.
-        let worker = Arc::try_unwrap(self.worker.take().unwrap()).map_err(|arc| {
-            PyValueError::new_err(format!(
-                "Cannot finalize, expected 1 reference, got {}",
-                Arc::strong_count(&arc)
-            ))
-        })?;
-        self.runtime.future_into_py(py, async move {
-            worker.finalize_shutdown().await;
-            Ok(())
-        })
+    fn available_slots(&self) -> Option<usize> {
+        self.inner.available_slots()
     }
 }
 
-fn convert_worker_config(
-    conf: WorkerConfig,
-    task_locals: Arc<OnceLock<pyo3_asyncio::TaskLocals>>,
-) -> PyResult<temporal_sdk_core::WorkerConfig> {
-    let converted_tuner = convert_tuner_holder(conf.tuner, task_locals)?;
-    temporal_sdk_core::WorkerConfigBuilder::default()
-        .namespace(conf.namespace)
-        .task_queue(conf.task_queue)
-        .worker_build_id(conf.build_id)
-        .client_identity_override(conf.identity_override)
-        .max_cached_workflows(conf.max_cached_workflows)
-        .max_concurrent_wft_polls(conf.max_concurrent_workflow_task_polls)
-        .tuner(Arc::new(converted_tuner))
-        .nonsticky_to_sticky_poll_ratio(conf.nonsticky_to_sticky_poll_ratio)
-        .max_concurrent_at_polls(conf.max_concurrent_activity_task_polls)
-        .no_remote_activities(conf.no_remote_activities)
-        .sticky_queue_schedule_to_start_timeout(Duration::from_millis(
-            conf.sticky_queue_schedule_to_start_timeout_millis,
+/// Slot type, event ("reserved", "used" or "released") and the number of slots
+/// of that type issued after the event
+type SlotEvent = (&'static str, &'static str, usize);
+
+/// Tuner reporting every slot state change of the wrapped tuner's suppliers
+struct InstrumentedTuner {
+    workflow: Arc<InstrumentedSlotSupplier<WorkflowSlotKind>>,
+    activity: Arc<InstrumentedSlotSupplier<ActivitySlotKind>>,
+    local_activity: Arc<InstrumentedSlotSupplier<LocalActivitySlotKind>>,
+    nexus: Arc<InstrumentedSlotSupplier<NexusSlotKind>>,
+}
+
+impl InstrumentedTuner {
+    fn new(inner: &dyn WorkerTuner, events: SlotEventSender) -> Self {
+        InstrumentedTuner {
+            workflow: InstrumentedSlotSupplier::new(
+                "workflow",
+                inner.workflow_task_slot_supplier(),
+                events.clone(),
+            ),
+            activity: InstrumentedSlotSupplier::new(
+                "activity",
+                inner.activity_task_slot_supplier(),
+                events.clone(),
+            ),
+            local_activity: InstrumentedSlotSupplier::new(
+                "local-activity",
+                inner.local_activity_slot_supplier(),
+                events.clone(),
+            ),
+            nexus: InstrumentedSlotSupplier::new("nexus", inner.nexus_task_slot_supplier(), events),
+        }
+    }
+}
+
+impl WorkerTuner for InstrumentedTuner {
+    fn workflow_task_slot_supplier(
+        &self,
+    ) -> Arc<dyn SlotSupplierTrait<SlotKind = WorkflowSlotKind> + Send + Sync> {
+        self.workflow.clone()
+    }
+
+    fn activity_task_slot_supplier(
+        &self,
+    ) -> Arc<dyn SlotSupplierTrait<SlotKind = ActivitySlotKind> + Send + Sync> {
+        self.activity.clone()
+    }
+
+    fn local_activity_slot_supplier(
+        &self,
+    ) -> Arc<dyn SlotSupplierTrait<SlotKind = LocalActivitySlotKind> + Send + Sync> {
+        self.local_activity.clone()
+    }
+
+    fn nexus_task_slot_supplier(
+        &self,
+    ) -> Arc<dyn SlotSupplierTrait<SlotKind = NexusSlotKind> + Send + Sync> {
+        self.nexus.clone()
+    }
+}
+
+struct InstrumentedSlotSupplier<SK: SlotKind> {
+    slot_type: &'static str,
+    inner: Arc<dyn SlotSupplierTrait<SlotKind = SK> + Send + Sync>,
+    issued: AtomicUsize,
+    events: SlotEventSender,
+}
+
+impl<SK: SlotKind> InstrumentedSlotSupplier<SK> {
+    fn new(
+        slot_type: &'static str,
+        inner: Arc<dyn SlotSupplierTrait<SlotKind = SK> + Send + Sync>,
+        events: SlotEventSender,
+    ) -> Arc<Self> {
+        Arc::new(InstrumentedSlotSupplier {
+            slot_type,
+            inner,
+            issued: AtomicUsize::new(0),
+            events,
+        })
+    }
+
+    fn emit(&self, event: &'static str, issued: usize) {
+        self.events.send((self.slot_type, event, issued));
+    }
+
+    fn reserved(&self) {
+        self.emit("reserved", self.issued.fetch_add(1, Ordering::SeqCst) + 1);
+    }
+}
+
+#[async_trait::async_trait]
+impl<SK: SlotKind + Send + Sync> SlotSupplierTrait for InstrumentedSlotSupplier<SK> {
+    type SlotKind = SK;
+
+    async fn reserve_slot(&self, ctx: &dyn SlotReservationContext) -> SlotSupplierPermit {
+        let permit = self.inner.reserve_slot(ctx).await;
+        self.reserved();
+        permit
+    }
+
+    fn try_reserve_slot(&self, ctx: &dyn SlotReservationContext) -> Option<SlotSupplierPermit> {
+        let permit = self.inner.try_reserve_slot(ctx);
+        if permit.is_some() {
+            self.reserved();
+        }
+        permit
+    }
+
+    fn mark_slot_used(&self, ctx: &dyn SlotMarkUsedContext<SlotKind = Self::SlotKind>) {
+        self.inner.mark_slot_used(ctx);
+        self.emit("used", self.issued.load(Ordering::SeqCst));
+    }
+
+    fn release_slot(&self, ctx: &dyn SlotReleaseContext<SlotKind = Self::SlotKind>) {
+        self.inner.release_slot(ctx);
+        self.emit("released", self.issued.fetch_sub(1, Ordering::SeqCst) - 1);
+    }
+
+    fn available_slots(&self) -> Option<usize> {
+        self.inner.available_slots()
+    }
+}
+
+/// How many unread slot events are buffered before new ones are dropped
+const SLOT_EVENT_BUFFER_SIZE: usize = 1024;
+
+/// Creates the sending and receiving ends for a worker's slot events
+fn slot_event_channel(runtime: &runtime::Runtime) -> (SlotEventSender, SlotEventReceiver) {
+    let (tx, rx) = channel(SLOT_EVENT_BUFFER_SIZE);
+    let dropped: Arc<AtomicU64> = Default::default();
+    (
+        SlotEventSender {
+            tx,
+            dropped: dropped.clone(),
+        },
+        SlotEventReceiver {
+            rx: Arc::new(tokio::sync::Mutex::new(rx)),
+            dropped,
+            runtime: runtime.clone(),
+        },
+    )
+}
+
+/// Sends slot events without ever waiting, dropping and counting them when
+/// the receiver has fallen behind so unread events cannot grow memory
+#[derive(Clone)]
+struct SlotEventSender {
+    tx: Sender<SlotEvent>,
+    dropped: Arc<AtomicU64>,
+}
+
+impl SlotEventSender {
+    fn send(&self, event: SlotEvent) {
+        match self.tx.try_send(event) {
+            Err(TrySendError::Full(_)) => {
+                self.dropped.fetch_add(1, Ordering::Relaxed);
+            }
+            // Nobody may be listening for events, which is fine
+            Ok(_) | Err(TrySendError::Closed(_)) => {}
+        }
+    }
+}
+
+/// Yields slot events of a worker created with slot events enabled. Up to
+/// `SLOT_EVENT_BUFFER_SIZE` events are buffered until read, later ones are
+/// dropped and counted.
+#[pyclass]
+#[derive(Clone)]
+pub struct SlotEventReceiver {
+    rx: Arc<tokio::sync::Mutex<Receiver<SlotEvent>>>,
+    dropped: Arc<AtomicU64>,
+    runtime: runtime::Runtime,
+}
+
+#[pymethods]
+impl SlotEventReceiver {
+    /// Number of events dropped because the buffer was full
+    #[getter]
+    fn dropped(&self) -> u64 {
+        self.dropped.load(Ordering::Relaxed)
+    }
+
+    /// Resolves to `(slot_type, event, issued_count)`, or `None` once the
+    /// worker is gone
+    fn next<'p>(&self, py: Python<'p>) -> PyResult<&'p PyAny> {
+        let rx = self.rx.clone();
+        self.runtime
+            .future_into_py(py, async move { Ok(rx.lock().await.recv().await) })
+    }
+}
+
+#[derive(FromPyObject, Clone, Copy, PartialEq)]
+pub struct ResourceBasedTunerConfig {
+    target_memory_usage: f64,
+    target_cpu_usage: f64,
+    // PID controller constants, core defaults are used when unset
+    memory_p_gain: Option<f64>,
+    memory_i_gain: Option<f64>,
+    memory_d_gain: Option<f64>,
+    memory_output_threshold: Option<f64>,
+    cpu_p_gain: Option<f64>,
+    cpu_i_gain: Option<f64>,
+    cpu_d_gain: Option<f64>,
+    cpu_output_threshold: Option<f64>,
+}
+
+impl ResourceBasedTunerConfig {
+    /// Name of the first field that differs from the other config, if any
+    fn first_mismatched_field(&self, other: &Self) -> Option<&'static str> {
+        if self.target_memory_usage != other.target_memory_usage {
+            Some("target_memory_usage")
+        } else if self.target_cpu_usage != other.target_cpu_usage {
+            Some("target_cpu_usage")
+        } else if self.memory_p_gain != other.memory_p_gain {
+            Some("memory_p_gain")
+        } else if self.memory_i_gain != other.memory_i_gain {
+            Some("memory_i_gain")
+        } else if self.memory_d_gain != other.memory_d_gain {
+            Some("memory_d_gain")
+        } else if self.memory_output_threshold != other.memory_output_threshold {
+            Some("memory_output_threshold")
+        } else if self.cpu_p_gain != other.cpu_p_gain {
+            Some("cpu_p_gain")
+        } else if self.cpu_i_gain != other.cpu_i_gain {
+            Some("cpu_i_gain")
+        } else if self.cpu_d_gain != other.cpu_d_gain {
+            Some("cpu_d_gain")
+        } else if self.cpu_output_threshold != other.cpu_output_threshold {
+            Some("cpu_output_threshold")
+        } else {
+            None
+        }
+    }
+}
+
+impl TryFrom<ResourceBasedTunerConfig> for temporal_sdk_core::ResourceBasedSlotsOptions {
+    type Error = PyErr;
+
+    fn try_from(conf: ResourceBasedTunerConfig) -> PyResult<Self> {
+        for (name, target) in [
+            ("target_memory_usage", conf.target_memory_usage),
+            ("target_cpu_usage", conf.target_cpu_usage),
+        ] {
+            if !(target > 0.0 && target <= 1.0) {
+                return Err(PyValueError::new_err(format!(
+                    "Resource-based tuner {} must be within (0.0, 1.0], got {}",
+                    name, target
+                )));
+            }
+        }
+        let mut build = temporal_sdk_core::ResourceBasedSlotsOptionsBuilder::default();
+        build
+            .target_mem_usage(conf.target_memory_usage)
+            .target_cpu_usage(conf.target_cpu_usage);
+        if let Some(v) = conf.memory_p_gain {
+            build.mem_p_gain(v);
+        }
+        if let Some(v) = conf.memory_i_gain {
+            build.mem_i_gain(v);
+        }
+        if let Some(v) = conf.memory_d_gain {
+            build.mem_d_gain(v);
+        }
+        if let Some(v) = conf.memory_output_threshold {
+            build.mem_output_threshold(v);
+        }
+        if let Some(v) = conf.cpu_p_gain {
+            build.cpu_p_gain(v);
+        }
+        if let Some(v) = conf.cpu_i_gain {
+            build.cpu_i_gain(v);
+        }
+        if let Some(v) = conf.cpu_d_gain {
+            build.cpu_d_gain(v);
+        }
+        if let Some(v) = conf.cpu_output_threshold {
+            build.cpu_output_threshold(v);
+        }
+        build.build().map_err(|err| {
+            PyValueError::new_err(format!("Invalid resource-based tuner config: {}", err))
+        })
+    }
+}
+
+macro_rules! enter_sync {
+    ($runtime:expr) => {
+        if let Some(subscriber) = $runtime.core.telemetry().trace_subscriber() {
+            temporal_sdk_core::telemetry::set_trace_subscriber_for_current_thread(subscriber);
+        }
+        let _guard = $runtime.core.tokio_handle().enter();
+    };
+}
+
+/// Everything `new_worker` derives from the config and client before creating
+/// the core worker, shared with `new_worker_dry_run` so both convert and
+/// validate identically
+struct PreparedWorker {
+    config: temporal_sdk_core::WorkerConfig,
+    client: client::Client,
+    event_loop_task_locals: Arc<OnceLock<pyo3_asyncio::TaskLocals>>,
+    namespace: String,
+    task_queue: String,
+    build_id: String,
+    completion_interceptor: Option<PyObject>,
+    tuner_config: serde_json::Value,
+    poller_behaviors: serde_json::Value,
+    poll_stats: Arc<PollStats>,
+    slot_events: Option<SlotEventReceiver>,
+}
+
+fn prepare_worker(
+    runtime_ref: &runtime::RuntimeRef,
+    client: &client::ClientRef,
+    mut config: WorkerConfig,
+) -> PyResult<PreparedWorker> {
+    let event_loop_task_locals = Arc::new(OnceLock::new());
+    let (namespace, task_queue, build_id) = config.identifiers();
+    let completion_interceptor = config.completion_interceptor.take();
+    let tuner_config = config.describe_tuner();
+    let poller_behaviors = config.describe_poller_behaviors();
+    let (slot_events_tx, slot_events) = config
+        .emit_slot_events
+        .then(|| slot_event_channel(&runtime_ref.runtime))
+        .unzip();
+    let poll_stats = Arc::new(PollStats::new(config.poll_watchdog));
+    let config = convert_worker_config(config, event_loop_task_locals.clone(), slot_events_tx)?;
+    Ok(PreparedWorker {
+        config,
+        client: client.retry_client.clone(),
+        event_loop_task_locals,
+        namespace,
+        task_queue,
+        build_id,
+        completion_interceptor,
+        tuner_config,
+        poller_behaviors,
+        poll_stats,
+        slot_events,
+    })
+}
+
+pub fn new_worker(
+    runtime_ref: &runtime::RuntimeRef,
+    client: &client::ClientRef,
+    config: WorkerConfig,
+) -> PyResult<WorkerRef> {
+    enter_sync!(runtime_ref.runtime);
+    let prepared = prepare_worker(runtime_ref, client, config)?;
+    let worker = temporal_sdk_core::init_worker(
+        &runtime_ref.runtime.core,
+        prepared.config,
+        prepared.client.clone().into_inner(),
+    )
+    .context("Failed creating worker")?;
+    runtime_ref
+        .runtime
+        .register_metric_snapshot(&prepared.namespace, &prepared.task_queue);
+    Ok(WorkerRef {
+        worker: Some(Arc::new(worker)),
+        worker_released: Default::default(),
+        event_loop_task_locals: prepared.event_loop_task_locals,
+        cached_run_ids: Default::default(),
+        activation_timings: Default::default(),
+        replay_results: None,
+        replay_channels: None,
+        outstanding_polls: Default::default(),
+        poll_stats: prepared.poll_stats,
+        parked_polls: Default::default(),
+        completion_interceptor: prepared.completion_interceptor,
+        slot_events: prepared.slot_events,
+        tuner_config: prepared.tuner_config,
+        poller_behaviors: prepared.poller_behaviors,
+        client: Mutex::new(Some(prepared.client)),
+        runtime: runtime_ref.runtime.clone(),
+        namespace: prepared.namespace,
+        task_queue: prepared.task_queue,
+        build_id: prepared.build_id,
+    })
+}
+
+/// Converts and validates the config with the client exactly as `new_worker`
+/// would, stopping before the core worker is created so nothing polls
+pub fn new_worker_dry_run(
+    runtime_ref: &runtime::RuntimeRef,
+    client: &client::ClientRef,
+    config: WorkerConfig,
+) -> PyResult<()> {
+    enter_sync!(runtime_ref.runtime);
+    prepare_worker(runtime_ref, client, config)?;
+    Ok(())
+}
+
+pub fn new_replay_worker<'a>(
+    py: Python<'a>,
+    runtime_ref: &runtime::RuntimeRef,
+    config: WorkerConfig,
+    history_buffer_size: usize,
+) -> PyResult<&'a PyTuple> {
+    enter_sync!(runtime_ref.runtime);
+    let (history_pusher, mut streams) =
+        HistoryPusher::new(runtime_ref.runtime.clone(), history_buffer_size, 1)?;
+    let (results_tx, results_rx) = unbounded_channel();
+    let channels = ReplayChannels {
+        histories: history_pusher.txs.clone(),
+        results: Arc::new(tokio::sync::Mutex::new(results_rx)),
+        run_ids: history_pusher.run_ids.clone(),
+    };
+    let result_receiver = ReplayResultReceiver {
+        rx: channels.results.clone(),
+        runtime: runtime_ref.runtime.clone(),
+    };
+    let worker =
+        new_replay_worker_ref(runtime_ref, config, streams.remove(0), results_tx, channels)?;
+    Ok(PyTuple::new(
+        py,
+        [
+            worker.into_py(py),
+            history_pusher.into_py(py),
+            result_receiver.into_py(py),
+        ],
+    ))
+}
+
+/// Same as `new_replay_worker` but creates `concurrency` replay workers. The
+/// single returned pusher round-robins histories across them and the single
+/// returned receiver yields results from all of them.
+pub fn new_replay_worker_pool<'a>(
+    py: Python<'a>,
+    runtime_ref: &runtime::RuntimeRef,
+    config: &PyAny,
+    concurrency: usize,
+    history_buffer_size: usize,
+) -> PyResult<&'a PyTuple> {
+    enter_sync!(runtime_ref.runtime);
+    if concurrency < 1 {
+        return Err(PyValueError::new_err(
+            "Replay worker pool concurrency must be at least 1",
+        ));
+    }
+    let (history_pusher, streams) = HistoryPusher::new(
+        runtime_ref.runtime.clone(),
+        history_buffer_size,
+        concurrency,
+    )?;
+    let (results_tx, results_rx) = unbounded_channel();
+    let channels = ReplayChannels {
+        histories: history_pusher.txs.clone(),
+        results: Arc::new(tokio::sync::Mutex::new(results_rx)),
+        run_ids: history_pusher.run_ids.clone(),
+    };
+    let result_receiver = ReplayResultReceiver {
+        rx: channels.results.clone(),
+        runtime: runtime_ref.runtime.clone(),
+    };
+    let workers = streams
+        .into_iter()
+        .map(|stream| {
+            // Config is extracted per worker since converted configs own
+            // things like slot suppliers that cannot be shared
+            new_replay_worker_ref(
+                runtime_ref,
+                config.extract()?,
+                stream,
+                results_tx.clone(),
+                channels.clone(),
+            )
+            .map(|worker| worker.into_py(py))
+        })
+        .collect::<PyResult<Vec<_>>>()?;
+    Ok(PyTuple::new(
+        py,
+        [
+            workers.into_py(py),
+            history_pusher.into_py(py),
+            result_receiver.into_py(py),
+        ],
+    ))
+}
+
+fn new_replay_worker_ref(
+    runtime_ref: &runtime::RuntimeRef,
+    mut config: WorkerConfig,
+    stream: ReceiverStream<HistoryForReplay>,
+    results_tx: UnboundedSender<ReplayResult>,
+    channels: ReplayChannels,
+) -> PyResult<WorkerRef> {
+    let event_loop_task_locals = Arc::new(OnceLock::new());
+    let (namespace, task_queue, build_id) = config.identifiers();
+    let completion_interceptor = config.completion_interceptor.take();
+    let tuner_config = config.describe_tuner();
+    let poller_behaviors = config.describe_poller_behaviors();
+    let poll_stats = Arc::new(PollStats::new(config.poll_watchdog));
+    // Slot events are only offered for regular workers
+    let config = convert_worker_config(config, event_loop_task_locals.clone(), None)?;
+    let worker = temporal_sdk_core::init_replay_worker(ReplayWorkerInput::new(config, stream))
+        .map_err(|err| PyValueError::new_err(format!("Failed creating replay worker: {}", err)))?;
+    runtime_ref
+        .runtime
+        .register_metric_snapshot(&namespace, &task_queue);
+    Ok(WorkerRef {
+        worker: Some(Arc::new(worker)),
+        worker_released: Default::default(),
+        event_loop_task_locals: Default::default(),
+        cached_run_ids: Default::default(),
+        activation_timings: Default::default(),
+        replay_results: Some(Arc::new(ReplayResultTracker {
+            runs: Default::default(),
+            run_ids: channels.run_ids.clone(),
+            tx: results_tx,
+        })),
+        replay_channels: Some(channels),
+        outstanding_polls: Default::default(),
+        poll_stats,
+        parked_polls: Default::default(),
+        completion_interceptor,
+        slot_events: None,
+        tuner_config,
+        poller_behaviors,
+        client: Default::default(),
+        runtime: runtime_ref.runtime.clone(),
+        namespace,
+        task_queue,
+        build_id,
+    })
+}
+
+impl WorkerRef {
+    /// Reference to the core worker for use by an in-flight call
+    fn worker_handle(&self) -> WorkerHandle {
+        WorkerHandle {
+            worker: Some(self.worker.as_ref().unwrap().clone()),
+            released: self.worker_released.clone(),
+        }
+    }
+
+    /// Set custom slot supplier task locals so they can run futures. Event
+    /// loop is assumed to be running at this point. Only the first call sets
+    /// them, since validation may be run more than once.
+    fn set_event_loop_task_locals(&self, py: Python) -> PyResult<()> {
+        if self.event_loop_task_locals.get().is_some() {
+            return Ok(());
+        }
+        let task_locals = pyo3_asyncio::TaskLocals::with_running_loop(py)?.copy_context(py)?;
+        // Cannot already be set since the GIL is held
+        let _ = self.event_loop_task_locals.set(task_locals);
+        Ok(())
+    }
+
+    /// Runs the completion interceptor, if any, over a workflow activation
+    /// completion. Every workflow completion entry point must go through
+    /// this, so completions built by the bridge are encoded for it too.
+    fn intercept_completion(
+        &self,
+        py: Python,
+        completion: WorkflowCompletion,
+    ) -> PyResult<WorkflowCompletion> {
+        let Some(interceptor) = &self.completion_interceptor else {
+            return Ok(completion);
+        };
+        // Called synchronously with the GIL held, so it adds a Python call and
+        // usually a re-encode to every completion
+        let bytes = match &completion {
+            WorkflowCompletion::Encoded(bytes) => PyBytes::new(py, bytes).into_py(py),
+            WorkflowCompletion::Decoded(completion) => encode_to_py_bytes(py, completion)?,
+        };
+        let intercepted = interceptor.call1(py, (bytes,))?;
+        Ok(WorkflowCompletion::Encoded(
+            intercepted
+                .as_ref(py)
+                .downcast::<PyBytes>()?
+                .as_bytes()
+                .to_vec(),
         ))
+    }
+
+    /// Intercepts and then completes a single workflow activation completion
+    fn intercept_and_complete<'p>(
+        &self,
+        py: Python<'p>,
+        completion: WorkflowCompletion,
+    ) -> PyResult<&'p PyAny> {
+        let completion = self.intercept_completion(py, completion)?;
+        let completer = self.workflow_completer();
+        self.runtime.future_into_py(py, async move {
+            // Decoded here so it happens without holding the GIL
+            completer.complete(completion.decode()?).await
+        })
+    }
+
+    fn poller(&self) -> Poller {
+        Poller {
+            worker: self.worker_handle(),
+            parked_polls: self.parked_polls.clone(),
+            poll_stats: self.poll_stats.clone(),
+            cached_run_ids: self.cached_run_ids.clone(),
+            activation_timings: self.activation_timings.clone(),
+            replay_results: self.replay_results.clone(),
+        }
+    }
+
+    fn workflow_completer(&self) -> WorkflowCompleter {
+        WorkflowCompleter {
+            worker: self.worker_handle(),
+            poll_stats: self.poll_stats.clone(),
+            activation_timings: self.activation_timings.clone(),
+        }
+    }
+}
+
+#[pymethods]
+impl WorkerRef {
+    fn validate<'p>(&self, py: Python<'p>) -> PyResult<&'p PyAny> {
+        let worker = self.worker_handle();
+        self.set_event_loop_task_locals(py)?;
+
+        self.runtime.future_into_py(py, async move {
+            worker
+                .validate()
+                .await
+                .context("Worker validation failed")
+                .map_err(Into::into)
+        })
+    }
+
+    /// Resolves to true once core's validation of the worker against the
+    /// server succeeds, or false if it has not within the timeout. Raises if
+    /// validation fails. Unlike `validate`, this can be called any number of
+    /// times, such as from a readiness probe.
+    fn wait_ready<'p>(&self, py: Python<'p>, timeout_millis: u64) -> PyResult<&'p PyAny> {
+        let worker = self.worker_handle();
+        self.runtime.future_into_py(py, async move {
+            match tokio::time::timeout(Duration::from_millis(timeout_millis), worker.validate())
+                .await
+            {
+                Ok(res) => res
+                    .context("Worker validation failed")
+                    .map(|_| true)
+                    .map_err(Into::into),
+                Err(_) => Ok(false),
+            }
+        })
+    }
+
+    /// Same as `validate` but resolves to a `WorkerValidation` with details
+    /// instead of raising on validation failure
+    fn validate_detailed<'p>(&self, py: Python<'p>) -> PyResult<&'p PyAny> {
+        let worker = self.worker_handle();
+        let client = self.client.lock().unwrap().clone();
+        self.set_event_loop_task_locals(py)?;
+
+        self.runtime.future_into_py(py, async move {
+            let error = worker
+                .validate()
+                .await
+                .err()
+                .map(|err| format!("Worker validation failed: {}", err));
+            let (namespace_exists, server_version) = if let Some(mut client) = client {
+                let namespace_exists = client
+                    .describe_namespace(tonic::Request::new(DescribeNamespaceRequest {
+                        namespace: worker.get_config().namespace.clone(),
+                        ..Default::default()
+                    }))
+                    .await
+                    .is_ok();
+                let server_version = client
+                    .get_system_info(tonic::Request::new(GetSystemInfoRequest::default()))
+                    .await
+                    .ok()
+                    .map(|resp| resp.into_inner().server_version);
+                (namespace_exists, server_version)
+            } else {
+                (false, None)
+            };
+            Ok(WorkerValidation {
+                error,
+                namespace_exists,
+                server_version,
+            })
+        })
+    }
+
+    /// Resolves to the round-trip time in milliseconds of a `GetSystemInfo`
+    /// call through the worker's client, raising `RPCError` if it fails
+    fn ping_server<'p>(&self, py: Python<'p>) -> PyResult<&'p PyAny> {
+        let Some(mut client) = self.client.lock().unwrap().clone() else {
+            return Err(PyRuntimeError::new_err("Replay workers have no client"));
+        };
+        self.runtime.future_into_py(py, async move {
+            let start = std::time::Instant::now();
+            client
+                .get_system_info(tonic::Request::new(GetSystemInfoRequest::default()))
+                .await
+                .map_err(client::rpc_error)?;
+            Ok(start.elapsed().as_secs_f64() * 1000.0)
+        })
+    }
+
+    /// Capability flags the worker's client got from the server's
+    /// `GetSystemInfo` when it connected, keyed by proto field name. None if
+    /// they have not been obtained.
+    fn server_capabilities(&self) -> PyResult<Option<HashMap<String, bool>>> {
+        let Some(client) = self.client.lock().unwrap().clone() else {
+            return Err(PyRuntimeError::new_err("Replay workers have no client"));
+        };
+        let Some(capabilities) = client.get_client().capabilities() else {
+            return Ok(None);
+        };
+        let value = serde_json::to_value(capabilities).map_err(|err| {
+            PyRuntimeError::new_err(format!("Failed reading server capabilities: {}", err))
+        })?;
+        Ok(value.as_object().map(|fields| {
+            fields
+                .iter()
+                .filter_map(|(name, flag)| Some((name.clone(), flag.as_bool()?)))
+                .collect()
+        }))
+    }
+
+    /// Dict keyed by "workflow", "activity" and "local_activity" describing
+    /// each slot supplier as given to core. Every entry has a "kind" of
+    /// "fixed_size" (with "num_slots"), "resource_based" (with slot bounds,
+    /// ramp throttle, usage targets and PID controller settings), "custom" or
+    /// "manual". The local activity entry also has the "max_per_second" rate
+    /// limit, `None` if unlimited.
+    fn effective_tuner_config(&self, py: Python) -> PyResult<PyObject> {
+        Ok(pythonize::pythonize(py, &self.tuner_config)?)
+    }
+
+    /// Dict keyed by "workflow" and "activity" describing each poller behavior
+    /// as given to core. Every entry has a "kind" of "simple_maximum" (with
+    /// "maximum") or "autoscaling" (with "minimum", "maximum" and
+    /// "initial"). The activity entry is `None` if activities are never polled.
+    fn effective_poller_behaviors(&self, py: Python) -> PyResult<PyObject> {
+        Ok(pythonize::pythonize(py, &self.poller_behaviors)?)
+    }
+
+    /// Resolves to backlog and poller stats for the worker's task queue of the
+    /// given type, "workflow" or "activity", raising `RPCError` if the
+    /// `DescribeTaskQueue` call fails
+    #[pyo3(signature = (task_queue_type="workflow"))]
+    fn describe_task_queue<'p>(
+        &self,
+        py: Python<'p>,
+        task_queue_type: &str,
+    ) -> PyResult<&'p PyAny> {
+        let Some(mut client) = self.client.lock().unwrap().clone() else {
+            return Err(PyRuntimeError::new_err("Replay workers have no client"));
+        };
+        let task_queue_type = match task_queue_type {
+            "workflow" => TaskQueueType::Workflow,
+            "activity" => TaskQueueType::Activity,
+            other => {
+                return Err(PyValueError::new_err(format!(
+                    "Unknown task queue type: {}",
+                    other
+                )))
+            }
+        };
+        let request = DescribeTaskQueueRequest {
+            namespace: self.namespace.clone(),
+            task_queue: Some(TaskQueue {
+                name: self.task_queue.clone(),
+                kind: TaskQueueKind::Normal as i32,
+                ..Default::default()
+            }),
+            task_queue_type: task_queue_type as i32,
+            report_stats: true,
+            ..Default::default()
+        };
+        self.runtime.future_into_py(py, async move {
+            let resp = client
+                .describe_task_queue(tonic::Request::new(request))
+                .await
+                .map_err(client::rpc_error)?
+                .into_inner();
+            let stats = resp.stats.unwrap_or_default();
+            Python::with_gil(|py| {
+                let pollers = resp
+                    .pollers
+                    .iter()
+                    .map(|poller| {
+                        let info = PyDict::new(py);
+                        info.set_item("identity", &poller.identity)?;
+                        info.set_item("rate_per_second", poller.rate_per_second)?;
+                        Ok(info)
+                    })
+                    .collect::<PyResult<Vec<_>>>()?;
+                let description = PyDict::new(py);
+                description.set_item("backlog_count", stats.approximate_backlog_count)?;
+                description.set_item("tasks_add_rate", stats.tasks_add_rate)?;
+                description.set_item("tasks_dispatch_rate", stats.tasks_dispatch_rate)?;
+                description.set_item("pollers", pollers)?;
+                Ok(description.to_object(py))
+            })
+        })
+    }
+
+    /// Pushes every `(workflow_id, history_proto)` pair, closes the history
+    /// pusher and resolves to a `{total, succeeded, failed, failures}` summary
+    /// once all of them have been replayed. Activations must still be polled
+    /// and completed meanwhile, and results collected here are not also given
+    /// to the result receiver.
+    fn run_replay_all<'p>(
+        &self,
+        py: Python<'p>,
+        histories: Vec<(String, &PyBytes)>,
+    ) -> PyResult<&'p PyAny> {
+        let Some(channels) = self.replay_channels.clone() else {
+            return Err(PyRuntimeError::new_err(
+                "Only replay workers can run replays",
+            ));
+        };
+        // Copied out so decoding can happen without holding the GIL
+        let histories: Vec<_> = histories
+            .into_iter()
+            .map(|(workflow_id, proto)| (workflow_id, proto.as_bytes().to_vec()))
+            .collect();
+        let histories = py
+            .allow_threads(|| {
+                histories
+                    .into_iter()
+                    .map(|(workflow_id, bytes)| {
+                        History::decode(bytes.as_slice())
+                            .map(|history| HistoryForReplay::new(history, workflow_id))
+                    })
+                    .collect::<Result<Vec<_>, _>>()
+            })
+            .map_err(invalid_proto::<History>)?;
+        let txs = std::mem::take(&mut *channels.histories.lock().unwrap());
+        if txs.is_empty() {
+            return Err(PyRuntimeError::new_err(
+                "Replay worker is no longer accepting new histories",
+            ));
+        }
+        self.runtime.future_into_py(py, async move {
+            let total = histories.len();
+            for (index, history) in histories.into_iter().enumerate() {
+                txs[index % txs.len()].send(history).await.map_err(|_| {
+                    PyRuntimeError::new_err(
+                        "Channel for history replay was dropped, this is an SDK bug.",
+                    )
+                })?;
+            }
+            // Dropping the last senders lets the workers shut down once done
+            drop(txs);
+            let mut results = channels.results.lock().await;
+            let mut failures = Vec::new();
+            for _ in 0..total {
+                let Some((workflow_id, success, error, ..)) = results.recv().await else {
+                    return Err(PyRuntimeError::new_err(
+                        "Replay workers stopped before all histories were replayed",
+                    ));
+                };
+                if !success {
+                    failures.push((workflow_id, error.unwrap_or_default()));
+                }
+            }
+            Python::with_gil(|py| {
+                let summary = PyDict::new(py);
+                summary.set_item("total", total)?;
+                summary.set_item("succeeded", total - failures.len())?;
+                summary.set_item("failed", failures.len())?;
+                summary.set_item("failures", failures)?;
+                Ok(summary.to_object(py))
+            })
+        })
+    }
+
+    fn poll_workflow_activation<'p>(&self, py: Python<'p>) -> PyResult<&'p PyAny> {
+        let poller = self.poller();
+        let poll_guard = self.outstanding_polls.start();
+        self.runtime.future_into_py(py, async move {
+            poll_guard.resumed().await;
+            match poller.poll_workflow_activation().await {
+                Ok((act, _)) => Python::with_gil(|py| encode_to_py_bytes(py, &act)),
+                Err(PollError::ShutDown) => Err(poll_guard.shutdown_error()),
+                Err(err) => Err(poll_failure(err)),
+            }
+        })
+    }
+
+    /// Same as `poll_workflow_activation` but resolves to a tuple of the
+    /// activation's run ID and its encoded bytes
+    fn poll_workflow_activation_with_run_id<'p>(&self, py: Python<'p>) -> PyResult<&'p PyAny> {
+        let poller = self.poller();
+        let poll_guard = self.outstanding_polls.start();
+        self.runtime.future_into_py(py, async move {
+            poll_guard.resumed().await;
+            match poller.poll_workflow_activation().await {
+                Ok((act, _)) => Python::with_gil(|py| {
+                    let bytes = encode_to_py_bytes(py, &act)?;
+                    Ok((act.run_id, bytes).into_py(py))
+                }),
+                Err(PollError::ShutDown) => Err(poll_guard.shutdown_error()),
+                Err(err) => Err(poll_failure(err)),
+            }
+        })
+    }
+
+    /// Same as `poll_workflow_activation` but resolves to a tuple of the
+    /// encoded activation, whether it came from the sticky queue and whether
+    /// its run was already cached. Core does not report which task queue an
+    /// activation was polled from, so the sticky flag is always `None`.
+    fn poll_workflow_activation_with_meta<'p>(&self, py: Python<'p>) -> PyResult<&'p PyAny> {
+        let poller = self.poller();
+        let poll_guard = self.outstanding_polls.start();
+        self.runtime.future_into_py(py, async move {
+            poll_guard.resumed().await;
+            match poller.poll_workflow_activation().await {
+                Ok((act, from_cache)) => Python::with_gil(|py| {
+                    let bytes = encode_to_py_bytes(py, &act)?;
+                    let is_sticky: Option<bool> = None;
+                    Ok((bytes, is_sticky, from_cache).into_py(py))
+                }),
+                Err(PollError::ShutDown) => Err(poll_guard.shutdown_error()),
+                Err(err) => Err(poll_failure(err)),
+            }
+        })
+    }
+
+    fn poll_activity_task<'p>(&self, py: Python<'p>) -> PyResult<&'p PyAny> {
+        let poller = self.poller();
+        let poll_guard = self.outstanding_polls.start();
+        self.runtime.future_into_py(py, async move {
+            poll_guard.resumed().await;
+            match poller.poll_activity_task().await {
+                Ok(task) => Python::with_gil(|py| encode_to_py_bytes(py, &task)),
+                Err(PollError::ShutDown) => Err(poll_guard.shutdown_error()),
+                Err(err) => Err(poll_failure(err)),
+            }
+        })
+    }
+
+    /// Same as `poll_activity_task` but resolves to `(activity_type,
+    /// task_token, bytes)` so callers can route the task without decoding it.
+    /// The activity type is empty for cancel tasks, which don't carry one.
+    fn poll_activity_task_with_type<'p>(&self, py: Python<'p>) -> PyResult<&'p PyAny> {
+        let poller = self.poller();
+        let poll_guard = self.outstanding_polls.start();
+        self.runtime.future_into_py(py, async move {
+            poll_guard.resumed().await;
+            match poller.poll_activity_task().await {
+                Ok(task) => {
+                    let activity_type = match &task.variant {
+                        Some(activity_task::Variant::Start(start)) => start.activity_type.clone(),
+                        _ => String::new(),
+                    };
+                    Python::with_gil(|py| {
+                        Ok((
+                            activity_type,
+                            PyBytes::new(py, &task.task_token).to_object(py),
+                            encode_to_py_bytes(py, &task)?,
+                        ))
+                    })
+                }
+                Err(PollError::ShutDown) => Err(poll_guard.shutdown_error()),
+                Err(err) => Err(poll_failure(err)),
+            }
+        })
+    }
+
+    /// Polls for a workflow activation and an activity task at once, resolving
+    /// to `("workflow", bytes)` or `("activity", bytes)` for whichever arrives
+    /// first. The other poll is kept and resumed by the next call instead of
+    /// being dropped. `PollShutdownError` is raised once both polls have seen
+    /// shutdown. Calls wait on each other, so only one should be outstanding.
+    fn poll_any<'p>(&self, py: Python<'p>) -> PyResult<&'p PyAny> {
+        let poller = self.poller();
+        let poll_guard = self.outstanding_polls.start();
+        let parked_polls = self.parked_polls.clone();
+        self.runtime.future_into_py(py, async move {
+            poll_guard.resumed().await;
+            let mut state = parked_polls.lock().await;
+            loop {
+                if let (PendingPoll::ShutDown, PendingPoll::ShutDown) =
+                    (&state.workflow, &state.activity)
+                {
+                    return Err(poll_guard.shutdown_error());
+                }
+                if let PendingPoll::Idle = state.workflow {
+                    state.workflow = PendingPoll::Polling(poller.start_workflow_poll());
+                }
+                if let PendingPoll::Idle = state.activity {
+                    state.activity = PendingPoll::Polling(poller.start_activity_poll());
+                }
+                let ParkedPolls { workflow, activity } = &mut *state;
+                let next = match futures::future::select(
+                    Box::pin(workflow.next()),
+                    Box::pin(activity.next()),
+                )
+                .await
+                {
+                    Either::Left((res, _)) => Either::Left(res),
+                    Either::Right((res, _)) => Either::Right(res),
+                };
+                match next {
+                    Either::Left(Ok((act, _))) => {
+                        return Python::with_gil(|py| {
+                            Ok(("workflow", encode_to_py_bytes(py, &act)?).into_py(py))
+                        });
+                    }
+                    Either::Right(Ok(task)) => {
+                        return Python::with_gil(|py| {
+                            Ok(("activity", encode_to_py_bytes(py, &task)?).into_py(py))
+                        });
+                    }
+                    Either::Left(Err(PollError::ShutDown))
+                    | Either::Right(Err(PollError::ShutDown)) => continue,
+                    Either::Left(Err(err)) | Either::Right(Err(err)) => {
+                        return Err(poll_failure(err))
+                    }
+                }
+            }
+        })
+    }
+
+    /// Same as `poll_workflow_activation` but resolves to `None` on shutdown
+    /// instead of raising `PollShutdownError`
+    fn poll_workflow_activation_opt<'p>(&self, py: Python<'p>) -> PyResult<&'p PyAny> {
+        let poller = self.poller();
+        let poll_guard = self.outstanding_polls.start();
+        self.runtime.future_into_py(py, async move {
+            poll_guard.resumed().await;
+            let _poll_guard = poll_guard;
+            match poller.poll_workflow_activation().await {
+                Ok((act, _)) => Python::with_gil(|py| encode_to_py_bytes(py, &act)).map(Some),
+                Err(PollError::ShutDown) => Ok(None),
+                Err(err) => Err(poll_failure(err)),
+            }
+        })
+    }
+
+    /// Same as `poll_activity_task` but resolves to `None` on shutdown instead
+    /// of raising `PollShutdownError`
+    fn poll_activity_task_opt<'p>(&self, py: Python<'p>) -> PyResult<&'p PyAny> {
+        let poller = self.poller();
+        let poll_guard = self.outstanding_polls.start();
+        self.runtime.future_into_py(py, async move {
+            poll_guard.resumed().await;
+            let _poll_guard = poll_guard;
+            match poller.poll_activity_task().await {
+                Ok(task) => Python::with_gil(|py| encode_to_py_bytes(py, &task)).map(Some),
+                Err(PollError::ShutDown) => Ok(None),
+                Err(err) => Err(poll_failure(err)),
+            }
+        })
+    }
+
+    /// Same as `poll_activity_task` but resolves to `None` if no task arrives
+    /// within the given timeout. The worker is left running so polling can be
+    /// resumed, and the in-flight poll is kept for the next activity poll to
+    /// resume.
+    fn poll_activity_task_timeout<'p>(
+        &self,
+        py: Python<'p>,
+        timeout_millis: u64,
+    ) -> PyResult<&'p PyAny> {
+        let poller = self.poller();
+        let poll_guard = self.outstanding_polls.start();
+        self.runtime.future_into_py(py, async move {
+            let mut poll = poller.take_activity_poll().await;
+            // Time spent paused counts towards the timeout
+            let res = tokio::time::timeout(Duration::from_millis(timeout_millis), async {
+                poll_guard.resumed().await;
+                (&mut poll).await
+            })
+            .await;
+            match res {
+                Ok(Ok(task)) => Python::with_gil(|py| encode_to_py_bytes(py, &task)).map(Some),
+                Ok(Err(PollError::ShutDown)) => Err(poll_guard.shutdown_error()),
+                Ok(Err(err)) => Err(poll_failure(err)),
+                Err(_) => {
+                    poller.park_activity_poll(poll).await;
+                    Ok(None)
+                }
+            }
+        })
+    }
+
+    fn activation_stream(&self) -> ActivationStream {
+        ActivationStream {
+            poller: self.poller(),
+            outstanding_polls: self.outstanding_polls.clone(),
+            runtime: self.runtime.clone(),
+        }
+    }
+
+    fn new_poll_canceller(&self) -> PollCanceller {
+        PollCanceller::default()
+    }
+
+    /// Same as `poll_workflow_activation` but resolves to `None` if the given
+    /// canceller is tripped before an activation arrives. The worker is left
+    /// running so polling can be resumed after resetting the canceller, and
+    /// the in-flight poll is kept for the next workflow poll to resume.
+    fn poll_workflow_activation_cancellable<'p>(
+        &self,
+        py: Python<'p>,
+        canceller: PollCanceller,
+    ) -> PyResult<&'p PyAny> {
+        let poller = self.poller();
+        let poll_guard = self.outstanding_polls.start();
+        self.runtime.future_into_py(py, async move {
+            let mut poll = poller.take_workflow_poll().await;
+            // Cancelling also stops waiting while paused
+            let res = {
+                let polled = std::pin::pin!(async {
+                    poll_guard.resumed().await;
+                    (&mut poll).await
+                });
+                let cancelled = std::pin::pin!(canceller.cancelled());
+                match futures::future::select(polled, cancelled).await {
+                    Either::Left((res, _)) => Some(res),
+                    Either::Right(_) => None,
+                }
+            };
+            match res {
+                Some(Ok((act, _))) => Python::with_gil(|py| encode_to_py_bytes(py, &act)).map(Some),
+                Some(Err(PollError::ShutDown)) => Err(poll_guard.shutdown_error()),
+                Some(Err(err)) => Err(poll_failure(err)),
+                None => {
+                    poller.park_workflow_poll(poll).await;
+                    Ok(None)
+                }
+            }
+        })
+    }
+
+    fn complete_workflow_activation<'p>(
+        &self,
+        py: Python<'p>,
+        proto: &PyBytes,
+    ) -> PyResult<&'p PyAny> {
+        self.intercept_and_complete(py, WorkflowCompletion::Encoded(proto.as_bytes().to_vec()))
+    }
+
+    /// Same as `complete_workflow_activation` but with the completion as JSON
+    /// in the same form core uses to serialize its protos. Only meant for
+    /// tooling.
+    fn complete_workflow_activation_json<'p>(
+        &self,
+        py: Python<'p>,
+        json: &str,
+    ) -> PyResult<&'p PyAny> {
+        let completion: WorkflowActivationCompletion =
+            serde_path_to_error::deserialize(&mut serde_json::Deserializer::from_str(json))
+                .map_err(|err| {
+                    PyValueError::new_err(format!(
+                        "Invalid completion JSON at {}: {}",
+                        err.path(),
+                        err.inner()
+                    ))
+                })?;
+        self.intercept_and_complete(py, WorkflowCompletion::Decoded(completion))
+    }
+
+    /// Same as `complete_workflow_activation` with a successful completion,
+    /// but assembled from the run ID and individually encoded commands so the
+    /// top-level completion never has to be encoded and decoded unless there
+    /// is a completion interceptor
+    fn complete_workflow_activation_commands<'p>(
+        &self,
+        py: Python<'p>,
+        run_id: String,
+        commands: Vec<&PyBytes>,
+    ) -> PyResult<&'p PyAny> {
+        let commands = commands
+            .into_iter()
+            .map(|command| WorkflowCommand::decode(command.as_bytes()))
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(invalid_proto::<WorkflowCommand>)?;
+        let completion = WorkflowActivationCompletion {
+            run_id,
+            status: Some(workflow_activation_completion::Status::Successful(
+                Success {
+                    commands,
+                    ..Default::default()
+                },
+            )),
+        };
+        self.intercept_and_complete(py, WorkflowCompletion::Decoded(completion))
+    }
+
+    /// Completes the given workflow activations, concurrently across runs but
+    /// one at a time and in the given order within a run. Resolves to a list
+    /// with, for each input in order, `None` on success or the exception
+    /// `complete_workflow_activation` would have raised for that input.
+    fn complete_workflow_activations<'p>(
+        &self,
+        py: Python<'p>,
+        protos: Vec<&PyBytes>,
+    ) -> PyResult<&'p PyAny> {
+        // The interceptor needs the GIL, so only decoding is left to the future
+        let completions = protos
+            .into_iter()
+            .map(|proto| {
+                self.intercept_completion(
+                    py,
+                    WorkflowCompletion::Encoded(proto.as_bytes().to_vec()),
+                )
+            })
+            .collect::<Vec<_>>();
+        let completer = self.workflow_completer();
+        self.runtime.future_into_py(py, async move {
+            let mut results: Vec<Option<PyErr>> = Vec::with_capacity(completions.len());
+            let mut runs: HashMap<String, Vec<(usize, WorkflowActivationCompletion)>> =
+                HashMap::new();
+            for (index, completion) in completions.into_iter().enumerate() {
+                match completion.and_then(WorkflowCompletion::decode) {
+                    Ok(completion) => {
+                        runs.entry(completion.run_id.clone())
+                            .or_default()
+                            .push((index, completion));
+                        results.push(None);
+                    }
+                    Err(err) => results.push(Some(err)),
+                }
+            }
+            let run_results = futures::future::join_all(runs.into_values().map(|run| {
+                let completer = completer.clone();
+                async move {
+                    let mut run_results = Vec::with_capacity(run.len());
+                    for (index, completion) in run {
+                        let result = completer.complete(completion).await;
+                        run_results.push((index, result.err()));
+                    }
+                    run_results
+                }
+            }))
+            .await;
+            for (index, result) in run_results.into_iter().flatten() {
+                results[index] = result;
+            }
+            Ok(results)
+        })
+    }
+
+    fn complete_activity_task<'p>(&self, py: Python<'p>, proto: &PyBytes) -> PyResult<&'p PyAny> {
+        let worker = self.worker_handle();
+        // Copied out so decoding can happen without holding the GIL
+        let bytes = proto.as_bytes().to_vec();
+        let completion = py
+            .allow_threads(|| ActivityTaskCompletion::decode(bytes.as_slice()))
+            .map_err(invalid_proto::<ActivityTaskCompletion>)?;
+        let poll_stats = self.poll_stats.clone();
+        self.runtime.future_into_py(py, async move {
+            worker
+                .complete_activity_task(completion)
+                .await
+                .map_err(completion_failure)?;
+            PollStats::increment(&poll_stats.activity_tasks_completed);
+            Ok(())
+        })
+    }
+
+    /// Fails the activity task with an application failure built from the
+    /// given message, optional error type, and optional encoded `Payloads`
+    /// details
+    #[pyo3(signature = (task_token, message, failure_type=None, details=None))]
+    fn complete_activity_task_failed<'p>(
+        &self,
+        py: Python<'p>,
+        task_token: &PyBytes,
+        message: String,
+        failure_type: Option<String>,
+        details: Option<&PyBytes>,
+    ) -> PyResult<&'p PyAny> {
+        let worker = self.worker_handle();
+        let details = details
+            .map(|details| Payloads::decode(details.as_bytes()))
+            .transpose()
+            .map_err(invalid_proto::<Payloads>)?;
+        let completion = ActivityTaskCompletion {
+            task_token: task_token.as_bytes().to_vec(),
+            result: Some(ActivityExecutionResult {
+                status: Some(activity_execution_result::Status::Failed(
+                    activity_result::Failure {
+                        failure: Some(Failure {
+                            message,
+                            failure_info: Some(FailureInfo::ApplicationFailureInfo(
+                                ApplicationFailureInfo {
+                                    r#type: failure_type.unwrap_or_default(),
+                                    details,
+                                    ..Default::default()
+                                },
+                            )),
+                            ..Default::default()
+                        }),
+                    },
+                )),
+            }),
+        };
+        let poll_stats = self.poll_stats.clone();
+        self.runtime.future_into_py(py, async move {
+            worker
+                .complete_activity_task(completion)
+                .await
+                .map_err(completion_failure)?;
+            PollStats::increment(&poll_stats.activity_tasks_completed);
+            Ok(())
+        })
+    }
+
+    /// Completes the activity task as cancelled, with a canceled failure
+    /// carrying the optional encoded `Payloads` details. Meant for
+    /// acknowledging a cancel request.
+    #[pyo3(signature = (task_token, details=None))]
+    fn complete_activity_task_cancelled<'p>(
+        &self,
+        py: Python<'p>,
+        task_token: &PyBytes,
+        details: Option<&PyBytes>,
+    ) -> PyResult<&'p PyAny> {
+        let worker = self.worker_handle();
+        let details = details
+            .map(|details| Payloads::decode(details.as_bytes()))
+            .transpose()
+            .map_err(invalid_proto::<Payloads>)?;
+        let completion = ActivityTaskCompletion {
+            task_token: task_token.as_bytes().to_vec(),
+            result: Some(ActivityExecutionResult {
+                status: Some(activity_execution_result::Status::Cancelled(
+                    activity_result::Cancellation {
+                        failure: Some(Failure {
+                            message: "Cancelled".to_owned(),
+                            failure_info: Some(FailureInfo::CanceledFailureInfo(
+                                CanceledFailureInfo {
+                                    details,
+                                    ..Default::default()
+                                },
+                            )),
+                            ..Default::default()
+                        }),
+                    },
+                )),
+            }),
+        };
+        let poll_stats = self.poll_stats.clone();
+        self.runtime.future_into_py(py, async move {
+            worker
+                .complete_activity_task(completion)
+                .await
+                .map_err(completion_failure)?;
+            PollStats::increment(&poll_stats.activity_tasks_completed);
+            Ok(())
+        })
+    }
+
+    /// Completes the given activity tasks concurrently. Resolves to a list
+    /// with, for each input in order, `None` on success or the exception
+    /// `complete_activity_task` would have raised for that input.
+    fn complete_activity_tasks<'p>(
+        &self,
+        py: Python<'p>,
+        protos: Vec<&PyBytes>,
+    ) -> PyResult<&'p PyAny> {
+        let worker = self.worker_handle();
+        // Copied out so decoding can happen without holding the GIL
+        let protos = protos
+            .into_iter()
+            .map(|proto| proto.as_bytes().to_vec())
+            .collect::<Vec<_>>();
+        let poll_stats = self.poll_stats.clone();
+        self.runtime.future_into_py(py, async move {
+            let results = futures::future::join_all(protos.into_iter().map(|proto| {
+                let worker = worker.clone();
+                let poll_stats = poll_stats.clone();
+                async move {
+                    let completion = ActivityTaskCompletion::decode(proto.as_slice())
+                        .map_err(invalid_proto::<ActivityTaskCompletion>)?;
+                    worker
+                        .complete_activity_task(completion)
+                        .await
+                        .map_err(completion_failure)?;
+                    PollStats::increment(&poll_stats.activity_tasks_completed);
+                    Ok::<_, PyErr>(())
+                }
+            }))
+            .await;
+            Ok(results
+                .into_iter()
+                .map(|result| result.err())
+                .collect::<Vec<_>>())
+        })
+    }
+
+    fn record_activity_heartbeat(&self, proto: &PyBytes) -> PyResult<()> {
+        enter_sync!(self.runtime);
+        let heartbeat = ActivityHeartbeat::decode(proto.as_bytes())
+            .map_err(invalid_proto::<ActivityHeartbeat>)?;
+        self.worker
+            .as_ref()
+            .unwrap()
+            .record_activity_heartbeat(heartbeat);
+        Ok(())
+    }
+
+    /// The optional reason is an eviction reason name such as
+    /// `LANG_REQUESTED`. Core does not accept a reason on lang-requested
+    /// evictions, so a given reason and message are only logged.
+    #[pyo3(signature = (run_id, reason=None, message=None))]
+    fn request_workflow_eviction(
+        &self,
+        run_id: &str,
+        reason: Option<&str>,
+        message: Option<&str>,
+    ) -> PyResult<()> {
+        enter_sync!(self.runtime);
+        let reason = reason
+            .map(|name| {
+                EvictionReason::from_str_name(&name.to_uppercase()).ok_or_else(|| {
+                    PyValueError::new_err(format!("Unknown eviction reason: {}", name))
+                })
+            })
+            .transpose()?;
+        if reason.is_some() || message.is_some() {
+            tracing::info!(
+                run_id,
+                reason = reason.map(|reason| reason.as_str_name()),
+                message,
+                "Requesting workflow eviction"
+            );
+        }
+        self.worker
+            .as_ref()
+            .unwrap()
+            .request_workflow_eviction(run_id);
+        Ok(())
+    }
+
+    /// Requests eviction of every workflow in `cached_run_ids`, returning how
+    /// many were requested. Core has no cache flush, and evictions only happen
+    /// once the resulting eviction activations are polled and completed.
+    fn evict_all_cached_workflows(&self) -> PyResult<usize> {
+        enter_sync!(self.runtime);
+        let worker = self.worker.as_ref().unwrap();
+        let run_ids = self.cached_run_ids();
+        for run_id in run_ids.iter() {
+            worker.request_workflow_eviction(run_id);
+        }
+        Ok(run_ids.len())
+    }
+
+    /// Resolves to true once an eviction for the run has been polled, or false
+    /// if none is polled within the timeout. Resolves to true immediately if
+    /// the run was recently evicted, and raises if this worker has neither
+    /// cached nor recently evicted the run.
+    fn await_eviction<'p>(
+        &self,
+        py: Python<'p>,
+        run_id: String,
+        timeout_millis: u64,
+    ) -> PyResult<&'p PyAny> {
+        if !self.cached_run_ids.is_known(&run_id) {
+            return Err(PyValueError::new_err(format!(
+                "Run {} is neither cached nor recently evicted by this worker",
+                run_id
+            )));
+        }
+        let cached_run_ids = self.cached_run_ids.clone();
+        self.runtime.future_into_py(py, async move {
+            Ok(tokio::time::timeout(
+                Duration::from_millis(timeout_millis),
+                cached_run_ids.evicted(&run_id),
+            )
+            .await
+            .is_ok())
+        })
+    }
+
+    /// Receiver of slot events, raising if they were not enabled in the config.
+    /// Every call returns a receiver reading the same events.
+    fn slot_event_receiver(&self) -> PyResult<SlotEventReceiver> {
+        self.slot_events.clone().ok_or_else(|| {
+            PyRuntimeError::new_err("Slot events were not enabled in the worker config")
+        })
+    }
+
+    /// Number of workflows currently cached, as observed from the activations
+    /// and evictions handed out by this worker
+    fn cached_workflow_count(&self) -> PyResult<usize> {
+        Ok(self.cached_run_ids.run_ids.lock().unwrap().len())
+    }
+
+    /// Core fixes the versioning strategy, including the build ID, when the
+    /// worker is created, so this only accepts the current build ID. A new
+    /// worker must be created to report a different one.
+    fn set_build_id(&self, build_id: &str) -> PyResult<()> {
+        if build_id != self.build_id {
+            return Err(PyRuntimeError::new_err(format!(
+                "Cannot change build ID from {} to {} after worker creation, create a new \
+                 worker instead",
+                self.build_id, build_id
+            )));
+        }
+        Ok(())
+    }
+
+    /// Identity the worker reports to the server, which is the configured
+    /// override if set or otherwise the identity of the worker's current client
+    fn effective_identity(&self) -> Option<String> {
+        let worker = self.worker.as_ref().unwrap();
+        worker
+            .get_config()
+            .client_identity_override
+            .clone()
+            .or_else(|| {
+                self.client
+                    .lock()
+                    .unwrap()
+                    .as_ref()
+                    .map(|client| client.get_client().options().identity.clone())
+            })
+    }
+
+    /// Run IDs of workflows currently cached, as observed the same way as
+    /// `cached_workflow_count`
+    fn cached_run_ids(&self) -> Vec<String> {
+        self.cached_run_ids
+            .run_ids
+            .lock()
+            .unwrap()
+            .iter()
+            .cloned()
+            .collect()
+    }
+
+    /// Sets or, with `None`, clears a callback invoked with `(run_id,
+    /// duration_millis)` each time a workflow activation completion succeeds,
+    /// measured from when that run's activation was polled. Only activations
+    /// polled while a callback is set are timed.
+    fn set_completion_timing_callback(&self, callback: Option<PyObject>) {
+        if callback.is_none() {
+            self.activation_timings.polled_at.lock().unwrap().clear();
+        }
+        *self.activation_timings.callback.lock().unwrap() = callback;
+    }
+
+    /// Counts of tasks polled and completed by this worker and of polls that
+    /// saw shutdown
+    fn poll_stats(&self) -> HashMap<&'static str, u64> {
+        self.poll_stats.to_map()
+    }
+
+    /// Seconds since a poll last returned a task, or since the worker was
+    /// created if none has yet. A value that keeps growing while work is
+    /// queued signals a stuck worker. `None` unless the poll watchdog was
+    /// enabled in the config.
+    fn seconds_since_last_poll(&self) -> Option<f64> {
+        self.poll_stats
+            .watchdog
+            .as_ref()
+            .map(PollWatchdog::seconds_since_last_poll)
+    }
+
+    fn get_metrics_snapshot<'p>(&self, py: Python<'p>) -> PyResult<&'p PyDict> {
+        let Some(worker) = self.worker.as_ref() else {
+            return Ok(PyDict::new(py));
+        };
+        let config = worker.get_config();
+        self.runtime
+            .metric_snapshot(py, &config.namespace, &config.task_queue)
+    }
+
+    /// Replaces the worker's client. Bridge clients are not bound to a
+    /// namespace, so callers pass the namespace the new client is used with.
+    /// It must match the worker's namespace and, unless the worker overrides
+    /// its identity, the new client's identity must match the current one's.
+    /// `force` skips both checks.
+    #[pyo3(signature = (client, namespace, force=false))]
+    fn replace_client(
+        &self,
+        client: &client::ClientRef,
+        namespace: &str,
+        force: bool,
+    ) -> PyResult<()> {
+        let worker = self.worker.as_ref().expect("missing worker");
+        let config = worker.get_config();
+        if !force {
+            if namespace != config.namespace {
+                return Err(PyValueError::new_err(format!(
+                    "New client namespace {} does not match worker namespace {}",
+                    namespace, config.namespace
+                )));
+            }
+            let new_identity = &client.retry_client.get_client().options().identity;
+            let current_identity = self
+                .client
+                .lock()
+                .unwrap()
+                .as_ref()
+                .map(|current| current.get_client().options().identity.clone());
+            if let (None, Some(current_identity)) =
+                (&config.client_identity_override, current_identity)
+            {
+                if *new_identity != current_identity {
+                    return Err(PyValueError::new_err(format!(
+                        "New client identity {} does not match worker identity {}",
+                        new_identity, current_identity
+                    )));
+                }
+            }
+        }
+        worker.replace_client(client.retry_client.clone().into_inner());
+        *self.client.lock().unwrap() = Some(client.retry_client.clone());
+        Ok(())
+    }
+
+    fn initiate_shutdown(&self) -> PyResult<()> {
+        let worker = self.worker_handle();
+        worker.initiate_shutdown();
+        self.outstanding_polls.initiate_shutdown();
+        Ok(())
+    }
+
+    /// Makes polls started from now on wait, without reaching core, until
+    /// `resume` is called or shutdown is initiated. Polls already in flight
+    /// and cached workflows are unaffected.
+    fn pause(&self) {
+        self.outstanding_polls.set_paused(true);
+    }
+
+    /// Lets paused and future polls proceed
+    fn resume(&self) {
+        self.outstanding_polls.set_paused(false);
+    }
+
+    fn is_paused(&self) -> bool {
+        self.outstanding_polls.paused.load(Ordering::SeqCst)
+    }
+
+    /// Current shutdown phase: "running", "initiated", "draining" once a
+    /// poll has seen core stop handing out tasks, or "complete" once core's
+    /// shutdown has finished
+    fn shutdown_phase(&self) -> &'static str {
+        self.outstanding_polls.shutdown_phase().name()
+    }
+
+    /// True once core's shutdown has finished, including in-flight tasks, or
+    /// the worker has been finalized
+    fn is_shutdown(&self) -> bool {
+        self.worker.is_none() || self.outstanding_polls.shutdown_phase() == ShutdownPhase::Complete
+    }
+
+    /// Initiates shutdown and resolves to true if the worker drained before
+    /// the deadline. Otherwise every cached workflow is evicted and this
+    /// resolves to false without waiting any longer.
+    ///
+    /// Outstanding activity slots are not abandoned at the deadline. Core has
+    /// no way to release a slot for a running activity, so activities are
+    /// still only bounded by the graceful shutdown timeout.
+    fn initiate_shutdown_with_deadline<'p>(
+        &self,
+        py: Python<'p>,
+        deadline_millis: u64,
+    ) -> PyResult<&'p PyAny> {
+        let worker = self.worker_handle();
+        worker.initiate_shutdown();
+        self.outstanding_polls.initiate_shutdown();
+        let outstanding_polls = self.outstanding_polls.clone();
+        let cached_run_ids = self.cached_run_ids.clone();
+        self.runtime.future_into_py(py, async move {
+            if tokio::time::timeout(Duration::from_millis(deadline_millis), worker.shutdown())
+                .await
+                .is_ok()
+            {
+                outstanding_polls.advance_shutdown(ShutdownPhase::Complete);
+                return Ok(true);
+            }
+            let run_ids = cached_run_ids
+                .run_ids
+                .lock()
+                .unwrap()
+                .iter()
+                .cloned()
+                .collect::<Vec<_>>();
+            tracing::warn!(
+                deadline_millis,
+                cached_workflows = run_ids.len(),
+                "Worker did not drain before shutdown deadline, evicting cached workflows"
+            );
+            for run_id in run_ids {
+                worker.request_workflow_eviction(&run_id);
+            }
+            Ok(false)
+        })
+    }
+
+    /// Resolves once shutdown has been initiated and no polls are in flight,
+    /// which may be well before outstanding tasks finish
+    fn wait_all_polls_drained<'p>(&self, py: Python<'p>) -> PyResult<&'p PyAny> {
+        let outstanding_polls = self.outstanding_polls.clone();
+        self.runtime.future_into_py(py, async move {
+            outstanding_polls.drained().await;
+            Ok(())
+        })
+    }
+
+    /// Resolves to true if shutdown completed, or false if the optional
+    /// timeout elapsed first
+    #[pyo3(signature = (timeout_millis=None))]
+    fn finalize_shutdown<'p>(
+        &mut self,
+        py: Python<'p>,
+        timeout_millis: Option<u64>,
+    ) -> PyResult<&'p PyAny> {
+        // Parked polls hold worker references but are never resumed once
+        // finalizing. Only a poll in progress holds the lock.
+        if let Ok(mut parked_polls) = self.parked_polls.try_lock() {
+            *parked_polls = Default::default();
+        }
+        // Take the worker out of the option and leave None. This should be the
+        // only reference remaining to the worker so try_unwrap will work.
+        let worker = Arc::try_unwrap(self.worker.take().unwrap()).map_err(|arc| {
+            PyValueError::new_err(format!(
+                "Cannot finalize, expected 1 reference, got {}",
+                Arc::strong_count(&arc)
+            ))
+        })?;
+        let outstanding_polls = self.outstanding_polls.clone();
+        let runtime = self.runtime.clone();
+        let namespace = worker.get_config().namespace.clone();
+        let task_queue = worker.get_config().task_queue.clone();
+        self.runtime.future_into_py(py, async move {
+            let finalized = if let Some(timeout_millis) = timeout_millis {
+                tokio::time::timeout(
+                    Duration::from_millis(timeout_millis),
+                    worker.finalize_shutdown(),
+                )
+                .await
+                .is_ok()
+            } else {
+                worker.finalize_shutdown().await;
+                true
+            };
+            if finalized {
+                outstanding_polls.advance_shutdown(ShutdownPhase::Complete);
+                runtime.clear_metric_snapshot(&namespace, &task_queue);
+            }
+            Ok(finalized)
+        })
+    }
+
+    /// Waits up to the given timeout for in-flight calls to drop their
+    /// references to the worker, so `finalize_shutdown` can then succeed.
+    /// Raises on timeout, leaving the worker usable.
+    fn wait_worker_released<'p>(&self, py: Python<'p>, timeout_millis: u64) -> PyResult<&'p PyAny> {
+        let worker = Arc::downgrade(self.worker.as_ref().unwrap());
+        let released = self.worker_released.clone();
+        let parked_polls = self.parked_polls.clone();
+        self.runtime.future_into_py(py, async move {
+            let all_released = async {
+                // Parked polls are never resumed once finalizing
+                *parked_polls.lock().await = Default::default();
+                loop {
+                    // Must create before checking so a change in between is not missed
+                    let changed = released.notified();
+                    // Only the reference held by this worker ref remains
+                    if worker.strong_count() <= 1 {
+                        return;
+                    }
+                    changed.await;
+                }
+            };
+            tokio::time::timeout(Duration::from_millis(timeout_millis), all_released)
+                .await
+                .map_err(|_| {
+                    PyValueError::new_err(format!(
+                        "Cannot finalize, timed out waiting for other worker references to be \
+                         dropped, {} remaining",
+                        worker.strong_count().saturating_sub(1)
+                    ))
+                })
+        })
+    }
+}
+
+/// Reference to the core worker held by an in-flight call, notifying the
+/// worker ref when dropped so finalization can wait for every call to let go
+#[derive(Clone)]
+struct WorkerHandle {
+    /// Only unset while dropping, so the reference is gone before notifying
+    worker: Option<Arc<temporal_sdk_core::Worker>>,
+    released: Arc<Notify>,
+}
+
+impl std::ops::Deref for WorkerHandle {
+    type Target = temporal_sdk_core::Worker;
+
+    fn deref(&self) -> &Self::Target {
+        self.worker.as_deref().unwrap()
+    }
+}
+
+impl Drop for WorkerHandle {
+    fn drop(&mut self) {
+        self.worker.take();
+        self.released.notify_waiters();
+    }
+}
+
+/// A workflow activation completion as received by one of the completion entry
+/// points, before interception
+enum WorkflowCompletion {
+    Encoded(Vec<u8>),
+    Decoded(WorkflowActivationCompletion),
+}
+
+impl WorkflowCompletion {
+    /// Called within futures so decoding does not hold the GIL
+    fn decode(self) -> PyResult<WorkflowActivationCompletion> {
+        match self {
+            WorkflowCompletion::Encoded(bytes) => {
+                WorkflowActivationCompletion::decode(bytes.as_slice())
+                    .map_err(invalid_proto::<WorkflowActivationCompletion>)
+            }
+            WorkflowCompletion::Decoded(completion) => Ok(completion),
+        }
+    }
+}
+
+/// Completes intercepted workflow activation completions with core, recording
+/// them in the poll stats and completion timings
+#[derive(Clone)]
+struct WorkflowCompleter {
+    worker: WorkerHandle,
+    poll_stats: Arc<PollStats>,
+    activation_timings: Arc<ActivationTimings>,
+}
+
+impl WorkflowCompleter {
+    async fn complete(&self, completion: WorkflowActivationCompletion) -> PyResult<()> {
+        let run_id = completion.run_id.clone();
+        let result = self
+            .worker
+            .complete_workflow_activation(completion)
+            .await
+            .map_err(completion_failure);
+        self.activation_timings.completed(run_id, result.is_ok());
+        result?;
+        PollStats::increment(&self.poll_stats.workflow_activations_completed);
+        Ok(())
+    }
+}
+
+/// Tracks polls in flight so callers can wait for them to drain once shutdown
+/// is initiated, and whether new polls are paused
+#[derive(Default)]
+struct OutstandingPolls {
+    count: AtomicUsize,
+    /// A `ShutdownPhase`, only ever advanced
+    shutdown_phase: AtomicU8,
+    paused: AtomicBool,
+    changed: Notify,
+}
+
+impl OutstandingPolls {
+    fn start(self: &Arc<Self>) -> OutstandingPollGuard {
+        self.count.fetch_add(1, Ordering::SeqCst);
+        OutstandingPollGuard(self.clone())
+    }
+
+    fn initiate_shutdown(&self) {
+        self.advance_shutdown(ShutdownPhase::Initiated);
+    }
+
+    fn advance_shutdown(&self, phase: ShutdownPhase) {
+        self.shutdown_phase.fetch_max(phase as u8, Ordering::SeqCst);
+        self.changed.notify_waiters();
+    }
+
+    fn shutdown_phase(&self) -> ShutdownPhase {
+        ShutdownPhase::from_u8(self.shutdown_phase.load(Ordering::SeqCst))
+    }
+
+    fn set_paused(&self, paused: bool) {
+        self.paused.store(paused, Ordering::SeqCst);
+        self.changed.notify_waiters();
+    }
+
+    /// Resolves once polling is not paused or shutdown has been initiated, so
+    /// paused polls still see shutdown
+    async fn resumed(&self) {
+        loop {
+            // Must create before checking so a change in between is not missed
+            let changed = self.changed.notified();
+            if !self.paused.load(Ordering::SeqCst)
+                || self.shutdown_phase() >= ShutdownPhase::Initiated
+            {
+                return;
+            }
+            changed.await;
+        }
+    }
+
+    fn is_drained(&self) -> bool {
+        self.shutdown_phase() >= ShutdownPhase::Initiated && self.count.load(Ordering::SeqCst) == 0
+    }
+
+    async fn drained(&self) {
+        loop {
+            // Must create before checking so a change in between is not missed
+            let changed = self.changed.notified();
+            if self.is_drained() {
+                return;
+            }
+            changed.await;
+        }
+    }
+}
+
+/// Polls core on behalf of every poll variant, so all of them count the poll
+/// and track what was polled the same way
+#[derive(Clone)]
+struct Poller {
+    worker: WorkerHandle,
+    parked_polls: Arc<tokio::sync::Mutex<ParkedPolls>>,
+    poll_stats: Arc<PollStats>,
+    cached_run_ids: Arc<CachedRuns>,
+    activation_timings: Arc<ActivationTimings>,
+    replay_results: Option<Arc<ReplayResultTracker>>,
+}
+
+impl Poller {
+    /// Resolves to the activation and whether its run was already cached.
+    /// Resumes the poll parked by a call that stopped waiting on it, if any.
+    async fn poll_workflow_activation(&self) -> Result<(WorkflowActivation, bool), PollError> {
+        self.take_workflow_poll().await.await
+    }
+
+    /// Takes the parked workflow poll, or starts a new one if there is none
+    async fn take_workflow_poll(&self) -> WorkflowPoll {
+        match std::mem::take(&mut self.parked_polls.lock().await.workflow) {
+            PendingPoll::Polling(poll) => poll,
+            _ => self.start_workflow_poll(),
+        }
+    }
+
+    /// Keeps a workflow poll the caller stopped waiting on, so the activation
+    /// it may already have taken from core is not lost
+    async fn park_workflow_poll(&self, poll: WorkflowPoll) {
+        self.parked_polls.lock().await.workflow = PendingPoll::Polling(poll);
+    }
+
+    fn start_workflow_poll(&self) -> WorkflowPoll {
+        let poller = self.clone();
+        Box::pin(async move {
+            let act = poller
+                .poll_stats
+                .count_workflow_poll(poller.worker.poll_workflow_activation())
+                .await?;
+            let from_cache = poller.on_workflow_activation(&act);
+            Ok((act, from_cache))
+        })
+    }
+
+    /// Resumes the poll parked by a call that stopped waiting on it, if any
+    async fn poll_activity_task(&self) -> Result<ActivityTask, PollError> {
+        self.take_activity_poll().await.await
+    }
+
+    /// Takes the parked activity poll, or starts a new one if there is none
+    async fn take_activity_poll(&self) -> ActivityPoll {
+        match std::mem::take(&mut self.parked_polls.lock().await.activity) {
+            PendingPoll::Polling(poll) => poll,
+            _ => self.start_activity_poll(),
+        }
+    }
+
+    /// Keeps an activity poll the caller stopped waiting on, so the task it
+    /// may already have taken from core is not lost
+    async fn park_activity_poll(&self, poll: ActivityPoll) {
+        self.parked_polls.lock().await.activity = PendingPoll::Polling(poll);
+    }
+
+    fn start_activity_poll(&self) -> ActivityPoll {
+        let poller = self.clone();
+        Box::pin(async move {
+            poller
+                .poll_stats
+                .count_activity_poll(poller.worker.poll_activity_task())
+                .await
+        })
+    }
+
+    /// Returns whether the run was already cached before this activation
+    fn on_workflow_activation(&self, act: &WorkflowActivation) -> bool {
+        let from_cache = track_cached_run(&self.cached_run_ids, act);
+        // Replaces any entry left by an earlier activation of the run, such as
+        // one evicted before it was completed
+        self.activation_timings.polled(&act.run_id);
+        if let Some(replay_results) = self.replay_results.as_ref() {
+            replay_results.track(act);
+        }
+        from_cache
+    }
+}
+
+type WorkflowPoll = BoxFuture<'static, Result<(WorkflowActivation, bool), PollError>>;
+type ActivityPoll = BoxFuture<'static, Result<ActivityTask, PollError>>;
+
+/// Polls that a call stopped waiting on, kept across calls so they are
+/// resumed rather than dropped. Used by `poll_any` for the poll that lost the
+/// race and by the cancellable and timeout polls.
+#[derive(Default)]
+struct ParkedPolls {
+    workflow: PendingPoll<(WorkflowActivation, bool)>,
+    activity: PendingPoll<ActivityTask>,
+}
+
+#[derive(Default)]
+enum PendingPoll<T> {
+    #[default]
+    Idle,
+    Polling(BoxFuture<'static, Result<T, PollError>>),
+    ShutDown,
+}
+
+impl<T> PendingPoll<T> {
+    /// Resolves when the outstanding poll does, never resolving if there is
+    /// none
+    async fn next(&mut self) -> Result<T, PollError> {
+        let PendingPoll::Polling(poll) = self else {
+            return futures::future::pending().await;
+        };
+        let res = poll.await;
+        *self = match res {
+            Err(PollError::ShutDown) => PendingPoll::ShutDown,
+            _ => PendingPoll::Idle,
+        };
+        res
+    }
+}
+
+/// Time of the last successful poll, as milliseconds since creation so it
+/// fits in an atomic
+struct PollWatchdog {
+    created: std::time::Instant,
+    last_poll_millis: AtomicU64,
+}
+
+impl PollWatchdog {
+    fn polled(&self) {
+        self.last_poll_millis
+            .store(self.created.elapsed().as_millis() as u64, Ordering::Relaxed);
+    }
+
+    fn seconds_since_last_poll(&self) -> f64 {
+        let last_poll = Duration::from_millis(self.last_poll_millis.load(Ordering::Relaxed));
+        self.created
+            .elapsed()
+            .saturating_sub(last_poll)
+            .as_secs_f64()
+    }
+}
+
+/// Lock-free counters of poll and completion outcomes
+#[derive(Default)]
+struct PollStats {
+    workflow_activations_polled: AtomicU64,
+    workflow_activations_completed: AtomicU64,
+    activity_tasks_polled: AtomicU64,
+    activity_tasks_completed: AtomicU64,
+    poll_shutdowns: AtomicU64,
+    /// Only set if the poll watchdog was enabled in the config
+    watchdog: Option<PollWatchdog>,
+}
+
+impl PollStats {
+    fn new(watchdog: bool) -> Self {
+        PollStats {
+            watchdog: watchdog.then(|| PollWatchdog {
+                created: std::time::Instant::now(),
+                last_poll_millis: AtomicU64::new(0),
+            }),
+            ..Default::default()
+        }
+    }
+
+    fn increment(counter: &AtomicU64) {
+        counter.fetch_add(1, Ordering::Relaxed);
+    }
+
+    async fn count_workflow_poll<T>(
+        &self,
+        poll: impl Future<Output = Result<T, PollError>>,
+    ) -> Result<T, PollError> {
+        self.count_poll(&self.workflow_activations_polled, poll)
+            .await
+    }
+
+    async fn count_activity_poll<T>(
+        &self,
+        poll: impl Future<Output = Result<T, PollError>>,
+    ) -> Result<T, PollError> {
+        self.count_poll(&self.activity_tasks_polled, poll).await
+    }
+
+    async fn count_poll<T>(
+        &self,
+        polled: &AtomicU64,
+        poll: impl Future<Output = Result<T, PollError>>,
+    ) -> Result<T, PollError> {
+        let result = poll.await;
+        match &result {
+            Ok(_) => {
+                Self::increment(polled);
+                if let Some(watchdog) = &self.watchdog {
+                    watchdog.polled();
+                }
+            }
+            Err(PollError::ShutDown) => Self::increment(&self.poll_shutdowns),
+            Err(_) => {}
+        }
+        result
+    }
+
+    fn to_map(&self) -> HashMap<&'static str, u64> {
+        HashMap::from([
+            (
+                "workflow_activations_polled",
+                self.workflow_activations_polled.load(Ordering::Relaxed),
+            ),
+            (
+                "workflow_activations_completed",
+                self.workflow_activations_completed.load(Ordering::Relaxed),
+            ),
+            (
+                "activity_tasks_polled",
+                self.activity_tasks_polled.load(Ordering::Relaxed),
+            ),
+            (
+                "activity_tasks_completed",
+                self.activity_tasks_completed.load(Ordering::Relaxed),
+            ),
+            (
+                "poll_shutdowns",
+                self.poll_shutdowns.load(Ordering::Relaxed),
+            ),
+        ])
+    }
+}
+
+/// Times workflow activations from poll to successful completion for the
+/// completion timing callback
+#[derive(Default)]
+struct ActivationTimings {
+    callback: Mutex<Option<PyObject>>,
+    polled_at: Mutex<HashMap<String, std::time::Instant>>,
+}
+
+impl ActivationTimings {
+    fn polled(&self, run_id: &str) {
+        if self.callback.lock().unwrap().is_some() {
+            self.polled_at
+                .lock()
+                .unwrap()
+                .insert(run_id.to_string(), std::time::Instant::now());
+        }
+    }
+
+    /// Removes the run's entry whatever the result, so runs whose completion
+    /// failed, or that were evicted, are not left behind. Only successful
+    /// completions are reported to the callback.
+    fn completed(&self, run_id: String, succeeded: bool) {
+        let Some(polled_at) = self.polled_at.lock().unwrap().remove(&run_id) else {
+            return;
+        };
+        if !succeeded {
+            return;
+        }
+        let duration_millis = polled_at.elapsed().as_secs_f64() * 1000.0;
+        Python::with_gil(|py| {
+            let callback = self
+                .callback
+                .lock()
+                .unwrap()
+                .as_ref()
+                .map(|callback| callback.clone_ref(py));
+            if let Some(callback) = callback {
+                if let Err(err) = callback.call1(py, (run_id, duration_millis)) {
+                    error!("Uncaught error in completion timing callback: {}", err);
+                }
+            }
+        });
+    }
+}
+
+/// Shutdown progress as observed by the bridge, in order
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[repr(u8)]
+enum ShutdownPhase {
+    Running,
+    /// Shutdown was requested through this worker ref
+    Initiated,
+    /// Core has stopped handing out tasks to at least one poller, though
+    /// in-flight tasks may still be running
+    Draining,
+    /// Core's shutdown, including waiting on in-flight tasks, has finished
+    Complete,
+}
+
+impl ShutdownPhase {
+    fn from_u8(value: u8) -> Self {
+        match value {
+            0 => ShutdownPhase::Running,
+            1 => ShutdownPhase::Initiated,
+            2 => ShutdownPhase::Draining,
+            _ => ShutdownPhase::Complete,
+        }
+    }
+
+    fn name(self) -> &'static str {
+        match self {
+            ShutdownPhase::Running => "running",
+            ShutdownPhase::Initiated => "initiated",
+            ShutdownPhase::Draining => "draining",
+            ShutdownPhase::Complete => "complete",
+        }
+    }
+}
+
+/// Counts as an outstanding poll until dropped, including when the poll future
+/// is dropped before completing
+struct OutstandingPollGuard(Arc<OutstandingPolls>);
+
+impl OutstandingPollGuard {
+    /// Error for this poll having seen shutdown, with the name of the
+    /// shutdown phase, which is at least draining now that core has stopped
+    /// handing out tasks to this poll
+    fn shutdown_error(&self) -> PyErr {
+        self.0.advance_shutdown(ShutdownPhase::Draining);
+        PollShutdownError::new_err(self.0.shutdown_phase().name())
+    }
+
+    /// Waits out a pause before this poll reaches core
+    async fn resumed(&self) {
+        self.0.resumed().await
+    }
+}
+
+impl Drop for OutstandingPollGuard {
+    fn drop(&mut self) {
+        if self.0.count.fetch_sub(1, Ordering::SeqCst) == 1 {
+            self.0.changed.notify_waiters();
+        }
+    }
+}
+
+/// Converts a non-shutdown poll error, distinguishing server/transport errors
+/// which are usually retryable from everything else
+fn poll_failure(err: PollError) -> PyErr {
+    let message = format!("Poll failure: {}", err);
+    if matches!(err, PollError::TonicError(_)) {
+        PollTransportError::new_err(message)
+    } else {
+        PollInternalError::new_err(message)
+    }
+}
+
+/// Converts a decode failure into an `InvalidProtoError` with the name of the
+/// message type that failed to decode as `message_type` and the decode error
+/// as `error`
+pub(crate) fn invalid_proto<M>(err: prost::DecodeError) -> PyErr {
+    let message_type = std::any::type_name::<M>()
+        .rsplit("::")
+        .next()
+        .unwrap_or_default();
+    Python::with_gil(|py| {
+        let py_err = InvalidProtoError::new_err(format!("Invalid {} proto: {}", message_type, err));
+        let value = py_err.value(py);
+        value.setattr("message_type", message_type)?;
+        value.setattr("error", err.to_string())?;
+        Ok(py_err)
+    })
+    .unwrap_or_else(|err| err)
+}
+
+/// Converts a completion error into a `CompletionError` whose `code` is the
+/// gRPC status code if the failure came from a server call, or `None` if not
+fn completion_failure(err: impl Into<anyhow::Error>) -> PyErr {
+    let err = err.into().context("Completion failure");
+    let code = err
+        .chain()
+        .find_map(|cause| cause.downcast_ref::<tonic::Status>())
+        .map(|status| status.code());
+    let mut message = format!("{:?}", err);
+    // Neither core's client nor the server allow raising the gRPC message
+    // size limit per worker, so the payloads themselves must shrink
+    if code == Some(tonic::Code::ResourceExhausted) {
+        message.push_str(
+            "\n\nThe completion may exceed the gRPC message size limit. Consider reducing the \
+             size of its payloads, for example by storing large data externally and passing a \
+             reference.",
+        );
+    }
+    let code = code.map(|code| code as i32);
+    Python::with_gil(|py| {
+        let py_err = CompletionError::new_err(message);
+        py_err.value(py).setattr("code", code)?;
+        Ok(py_err)
+    })
+    .unwrap_or_else(|err| err)
+}
+
+/// Returns whether the run was already cached before this activation
+fn track_cached_run(cached_run_ids: &CachedRuns, act: &WorkflowActivation) -> bool {
+    let is_eviction = act.jobs.iter().any(|job| {
+        matches!(
+            job.variant,
+            Some(workflow_activation_job::Variant::RemoveFromCache(_))
+        )
+    });
+    let mut run_ids = cached_run_ids.run_ids.lock().unwrap();
+    if is_eviction {
+        let was_cached = run_ids.remove(&act.run_id);
+        let mut recently_evicted = cached_run_ids.recently_evicted.lock().unwrap();
+        if recently_evicted.len() >= RECENTLY_EVICTED_RUNS_LIMIT {
+            recently_evicted.pop_front();
+        }
+        recently_evicted.push_back(act.run_id.clone());
+        cached_run_ids.evicted.notify_waiters();
+        was_cached
+    } else {
+        !run_ids.insert(act.run_id.clone())
+    }
+}
+
+/// How many evicted run IDs are remembered so waiting on an eviction that was
+/// already polled resolves instead of raising
+const RECENTLY_EVICTED_RUNS_LIMIT: usize = 1000;
+
+/// Cached run IDs, with a notification whenever an eviction is polled
+#[derive(Default)]
+struct CachedRuns {
+    run_ids: Mutex<HashSet<String>>,
+    /// Oldest first, bounded by [RECENTLY_EVICTED_RUNS_LIMIT]. Always locked
+    /// after `run_ids` when both are held.
+    recently_evicted: Mutex<VecDeque<String>>,
+    evicted: Notify,
+}
+
+impl CachedRuns {
+    /// Whether the run is cached or was recently evicted
+    fn is_known(&self, run_id: &str) -> bool {
+        let run_ids = self.run_ids.lock().unwrap();
+        run_ids.contains(run_id)
+            || self
+                .recently_evicted
+                .lock()
+                .unwrap()
+                .iter()
+                .any(|evicted| evicted == run_id)
+    }
+
+    /// Resolves once the run is not cached, immediately if it already isn't
+    async fn evicted(&self, run_id: &str) {
+        loop {
+            // Must create before checking so a change in between is not missed
+            let evicted = self.evicted.notified();
+            if !self.run_ids.lock().unwrap().contains(run_id) {
+                return;
+            }
+            evicted.await;
+        }
+    }
+}
+
+/// Workflow ID, success, error, the last event ID lang was given and run ID
+type ReplayResult = (String, bool, Option<String>, u32, String);
+
+/// History senders and result receiver shared between replay workers and the
+/// pusher and receiver created with them, so a worker can run a whole batch
+#[derive(Clone)]
+struct ReplayChannels {
+    histories: Arc<Mutex<Vec<Sender<HistoryForReplay>>>>,
+    results: Arc<tokio::sync::Mutex<UnboundedReceiver<ReplayResult>>>,
+    run_ids: Arc<Mutex<HashMap<String, String>>>,
+}
+
+/// Correlates replay evictions back to the workflow IDs of pushed histories
+struct ReplayResultTracker {
+    /// Workflow IDs and the history length of the latest activation, which is
+    /// the last event ID processed, keyed by run ID for histories still being
+    /// replayed
+    runs: Mutex<HashMap<String, (String, u32)>>,
+    /// Run IDs given when pushing histories, keyed by the run ID core uses
+    run_ids: Arc<Mutex<HashMap<String, String>>>,
+    tx: UnboundedSender<ReplayResult>,
+}
+
+impl ReplayResultTracker {
+    fn track(&self, act: &WorkflowActivation) {
+        if let Some((_, last_event_id)) = self.runs.lock().unwrap().get_mut(&act.run_id) {
+            *last_event_id = act.history_length.max(*last_event_id);
+        }
+        for job in act.jobs.iter() {
+            match job.variant.as_ref() {
+                Some(workflow_activation_job::Variant::InitializeWorkflow(init)) => {
+                    self.runs.lock().unwrap().insert(
+                        act.run_id.clone(),
+                        (init.workflow_id.clone(), act.history_length),
+                    );
+                }
+                Some(workflow_activation_job::Variant::RemoveFromCache(remove)) => {
+                    let Some((workflow_id, last_event_id)) =
+                        self.runs.lock().unwrap().remove(&act.run_id)
+                    else {
+                        continue;
+                    };
+                    // Same eviction reasons the Python replayer considers
+                    // successful
+                    let success = matches!(
+                        remove.reason(),
+                        EvictionReason::CacheFull | EvictionReason::LangRequested
+                    );
+                    let error = (!success)
+                        .then(|| format!("{}: {}", remove.reason().as_str_name(), remove.message));
+                    let run_id = self
+                        .run_ids
+                        .lock()
+                        .unwrap()
+                        .remove(&act.run_id)
+                        .unwrap_or_else(|| act.run_id.clone());
+                    // Nobody may be listening for results, which is fine
+                    let _ = self
+                        .tx
+                        .send((workflow_id, success, error, last_event_id, run_id));
+                }
+                _ => {}
+            }
+        }
+    }
+}
+
+/// Encodes the message directly into a new Python bytes object, avoiding the
+/// intermediate Vec and copy of `encode_to_vec`
+fn encode_to_py_bytes<M: Message>(py: Python<'_>, msg: &M) -> PyResult<PyObject> {
+    let bytes = PyBytes::new_with(py, msg.encoded_len(), |mut buf: &mut [u8]| {
+        msg.encode(&mut buf)
+            .map_err(|err| PyRuntimeError::new_err(format!("Failed encoding proto: {}", err)))
+    })?;
+    Ok(bytes.into_py(py))
+}
+
+fn convert_worker_config(
+    conf: WorkerConfig,
+    task_locals: Arc<OnceLock<pyo3_asyncio::TaskLocals>>,
+    slot_events: Option<SlotEventSender>,
+) -> PyResult<temporal_sdk_core::WorkerConfig> {
+    if conf.disable_sticky_queues && conf.max_cached_workflows != 0 {
+        return Err(PyValueError::new_err(format!(
+            "Cannot disable sticky queues with nonzero max cached workflows ({}), \
+             sticky queues require the workflow cache so max cached workflows must be 0",
+            conf.max_cached_workflows
+        )));
+    }
+    // Also rejects NaN
+    if !(0.0..=1.0).contains(&conf.nonsticky_to_sticky_poll_ratio) {
+        return Err(PyValueError::new_err(format!(
+            "Nonsticky to sticky poll ratio must be between 0.0 and 1.0, got {}",
+            conf.nonsticky_to_sticky_poll_ratio
+        )));
+    }
+    // A default interval is always given, so a zero max is never valid
+    if conf.max_heartbeat_throttle_interval_millis == 0 {
+        return Err(PyValueError::new_err(
+            "Max heartbeat throttle interval must be greater than zero",
+        ));
+    }
+    if conf.default_heartbeat_throttle_interval_millis > conf.max_heartbeat_throttle_interval_millis
+    {
+        return Err(PyValueError::new_err(format!(
+            "Default heartbeat throttle interval ({}ms) cannot exceed max heartbeat throttle \
+             interval ({}ms)",
+            conf.default_heartbeat_throttle_interval_millis,
+            conf.max_heartbeat_throttle_interval_millis
+        )));
+    }
+    let versioning_strategy = match conf.deployment_options {
+        Some(_) if conf.use_worker_versioning => {
+            return Err(PyValueError::new_err(
+                "Cannot set both use_worker_versioning and deployment_options",
+            ));
+        }
+        Some(opts) => WorkerVersioningStrategy::WorkerDeploymentBased(opts.try_into()?),
+        None if conf.use_worker_versioning => WorkerVersioningStrategy::LegacyBuildIdBased {
+            build_id: conf.build_id,
+        },
+        None => WorkerVersioningStrategy::None {
+            build_id: conf.build_id,
+        },
+    };
+    let mut tuner: Arc<dyn WorkerTuner + Send + Sync> =
+        Arc::new(convert_tuner_holder(conf.tuner, task_locals)?);
+    if let Some(per_second) = conf.max_local_activities_per_second {
+        // Also rejects rates too small for the interval to be representable
+        let interval = Duration::try_from_secs_f64(1.0 / per_second)
+            .ok()
+            .filter(|_| per_second.is_finite() && per_second > 0.0)
+            .ok_or_else(|| {
+                PyValueError::new_err(format!(
+                    "Max local activities per second must be greater than zero, got {}",
+                    per_second
+                ))
+            })?;
+        tuner = Arc::new(LocalActivityRateLimitedTuner::new(tuner, interval));
+    }
+    if let Some(tx) = slot_events {
+        tuner = Arc::new(InstrumentedTuner::new(tuner.as_ref(), tx));
+    }
+    let mut builder = temporal_sdk_core::WorkerConfigBuilder::default();
+    builder
+        .namespace(conf.namespace)
+        .task_queue(conf.task_queue)
+        .versioning_strategy(versioning_strategy)
+        .client_identity_override(conf.identity_override)
+        .max_cached_workflows(conf.max_cached_workflows)
+        .workflow_task_poller_behavior(conf.workflow_task_poller_behavior.try_into()?)
+        .tuner(tuner)
+        .nonsticky_to_sticky_poll_ratio(conf.nonsticky_to_sticky_poll_ratio)
+        .no_remote_activities(conf.no_remote_activities)
         .max_heartbeat_throttle_interval(Duration::from_millis(
             conf.max_heartbeat_throttle_interval_millis,
         ))
         .default_heartbeat_throttle_interval(Duration::from_millis(
             conf.default_heartbeat_throttle_interval_millis,
         ))
+        .local_timeout_buffer_for_activities(Duration::from_millis(
+            conf.local_timeout_buffer_for_activities_millis,
+        ))
         .max_worker_activities_per_second(conf.max_activities_per_second)
         .max_task_queue_activities_per_second(conf.max_task_queue_activities_per_second)
-        // Even though grace period is optional, if it is not set then the
-        // auto-cancel-activity behavior of shutdown will not occur, so we
-        // always set it even if 0.
-        .graceful_shutdown_period(Duration::from_millis(conf.graceful_shutdown_period_millis))
-        .use_worker_versioning(conf.use_worker_versioning)
-        .workflow_failure_errors(if conf.nondeterminism_as_workflow_fail {
-            HashSet::from([WorkflowErrorType::Nondeterminism])
-        } else {
-            HashSet::new()
-        })
+        .workflow_failure_errors(convert_workflow_error_types(conf.workflow_failure_errors)?)
         .workflow_types_to_failure_errors(
-            conf.nondeterminism_as_workflow_fail_for_types
-                .iter()
-                .map(|s| {
-                    (
-                        s.to_owned(),
-                        HashSet::from([WorkflowErrorType::Nondeterminism]),
-                    )
+            conf.workflow_types_to_failure_errors
+                .into_iter()
+                .map(|(workflow_type, errors)| {
+                    Ok((workflow_type, convert_workflow_error_types(errors)?))
                 })
-                .collect::<HashMap<String, HashSet<WorkflowErrorType>>>(),
-        )
+                .collect::<PyResult<HashMap<String, HashSet<WorkflowErrorType>>>>()?,
+        );
+    // If the grace period is not set then the auto-cancel-activity behavior of
+    // shutdown will not occur, so callers wanting it must set it even if 0
+    if let Some(millis) = conf.graceful_shutdown_period_millis {
+        builder.graceful_shutdown_period(Duration::from_millis(millis));
+    }
+    if !conf.disable_sticky_queues {
+        builder.sticky_queue_schedule_to_start_timeout(Duration::from_millis(
+            conf.sticky_queue_schedule_to_start_timeout_millis,
+        ));
+    }
+    // Activity poller behavior is irrelevant if we never poll for activities,
+    // so we don't set it (and therefore don't validate it) in that case
+    if !conf.no_remote_activities {
+        builder.activity_task_poller_behavior(conf.activity_task_poller_behavior.try_into()?);
+    }
+    builder
         .build()
         .map_err(|err| PyValueError::new_err(format!("Invalid worker config: {}", err)))
 }
 
+fn convert_workflow_error_types(names: HashSet<String>) -> PyResult<HashSet<WorkflowErrorType>> {
+    names
+        .into_iter()
+        .map(|name| match name.as_str() {
+            "Nondeterminism" => Ok(WorkflowErrorType::Nondeterminism),
+            _ => Err(PyValueError::new_err(format!(
+                "Unknown workflow error type: {}",
+                name
+            ))),
+        })
+        .collect()
+}
+
+/// Core's resource-based controller gets memory and CPU usage from procfs on
+/// Linux and has nothing meaningful to act on when sandboxing hides it. Warn
+/// loudly rather than fail, since the worker itself still works.
+fn warn_if_system_metrics_unreadable() {
+    if !cfg!(target_os = "linux") {
+        return;
+    }
+    let mem_total = std::fs::read_to_string("/proc/meminfo")
+        .ok()
+        .and_then(|meminfo| {
+            meminfo
+                .lines()
+                .find_map(|line| line.strip_prefix("MemTotal:"))
+                .and_then(|rest| {
+                    rest.trim()
+                        .trim_end_matches("kB")
+                        .trim()
+                        .parse::<u64>()
+                        .ok()
+                })
+        });
+    let problem = match mem_total {
+        None | Some(0) => "memory usage could not be read from /proc/meminfo",
+        Some(_) if std::fs::read_to_string("/proc/stat").is_err() => {
+            "CPU usage could not be read from /proc/stat"
+        }
+        Some(_) => return,
+    };
+    tracing::warn!(
+        "Resource-based tuning will not work in this environment, {}. Slot counts \
+         will not track actual usage; consider fixed-size slot suppliers instead.",
+        problem
+    );
+}
+
 fn convert_tuner_holder(
     holder: TunerHolder,
     task_locals: Arc<OnceLock<pyo3_asyncio::TaskLocals>>,
 ) -> PyResult<temporal_sdk_core::TunerHolder> {
-    // Verify all resource-based options are the same if any are set
-    let maybe_wf_resource_opts =
-        if let SlotSupplier::ResourceBased(ref ss) = holder.workflow_slot_supplier {
-            Some(&ss.tuner_config)
-        } else {
-            None
-        };
-    let maybe_act_resource_opts =
-        if let SlotSupplier::ResourceBased(ref ss) = holder.activity_slot_supplier {
-            Some(&ss.tuner_config)
-        } else {
-            None
-        };
-    let maybe_local_act_resource_opts =
-        if let SlotSupplier::ResourceBased(ref ss) = holder.local_activity_slot_supplier {
-            Some(&ss.tuner_config)
-        } else {
-            None
-        };
+    // Core uses a single resource-based controller for every resource-based
+    // slot supplier, so all of the ones that are set must agree on options
     let all_resource_opts = [
-        maybe_wf_resource_opts,
-        maybe_act_resource_opts,
-        maybe_local_act_resource_opts,
-    ];
-    let mut set_resource_opts = all_resource_opts.iter().flatten();
-    let first = set_resource_opts.next();
-    let all_are_same = if let Some(first) = first {
-        set_resource_opts.all(|elem| elem == first)
-    } else {
-        true
-    };
-    if !all_are_same {
-        return Err(PyValueError::new_err(
-            "All resource-based slot suppliers must have the same ResourceBasedTunerOptions",
-        ));
+        ("workflow", &holder.workflow_slot_supplier),
+        ("activity", &holder.activity_slot_supplier),
+        ("local activity", &holder.local_activity_slot_supplier),
+    ]
+    .into_iter()
+    .filter_map(|(slot_type, supplier)| match supplier {
+        SlotSupplier::ResourceBased(ss) => Some((slot_type, ss.tuner_config)),
+        _ => None,
+    })
+    .collect::<Vec<_>>();
+    let first = all_resource_opts.first().map(|(_, opts)| *opts);
+    if let Some((first_slot_type, first_opts)) = all_resource_opts.first() {
+        for (slot_type, opts) in all_resource_opts.iter().skip(1) {
+            if let Some(field) = first_opts.first_mismatched_field(opts) {
+                return Err(PyValueError::new_err(format!(
+                    "All resource-based slot suppliers must have the same ResourceBasedTunerOptions, \
+                     but {} and {} slot suppliers differ on {}",
+                    first_slot_type, slot_type, field
+                )));
+            }
+        }
     }
 
     let mut options = temporal_sdk_core::TunerHolderOptionsBuilder::default();
     if let Some(first) = first {
-        options.resource_based_options(
-            temporal_sdk_core::ResourceBasedSlotsOptionsBuilder::default()
-                .target_mem_usage(first.target_memory_usage)
-                .target_cpu_usage(first.target_cpu_usage)
-                .build()
-                .expect("Building ResourceBasedSlotsOptions is infallible"),
-        );
+        options.resource_based_options(first.try_into()?);
+        warn_if_system_metrics_unreadable();
     };
     options
         .workflow_slot_options(convert_slot_supplier(
@@ -699,61 +3552,304 @@ fn convert_slot_supplier<SK: SlotKind + Send + Sync + 'static>(
                 _phantom: PhantomData,
             },
         )),
+        SlotSupplier::Manual(ms) => temporal_sdk_core::SlotSupplierOptions::Custom(Arc::new(
+            ManualSlotSupplierOfType::<SK> {
+                gate: ms.gate,
+                _phantom: PhantomData,
+            },
+        )),
     })
 }
 
 /// For feeding histories into core during replay
 #[pyclass]
 pub struct HistoryPusher {
-    tx: Option<Sender<HistoryForReplay>>,
+    /// One sender per replay worker, empty once closed
+    txs: Arc<Mutex<Vec<Sender<HistoryForReplay>>>>,
+    /// Index of the sender the next history is pushed to
+    next_tx: AtomicUsize,
+    /// Run IDs given when pushing that differ from the one core extracts from
+    /// the history, keyed by the extracted one
+    run_ids: Arc<Mutex<HashMap<String, String>>>,
     runtime: runtime::Runtime,
 }
 
 impl HistoryPusher {
-    fn new(runtime: runtime::Runtime) -> (Self, ReceiverStream<HistoryForReplay>) {
-        let (tx, rx) = channel(1);
-        (
+    fn new(
+        runtime: runtime::Runtime,
+        buffer_size: usize,
+        worker_count: usize,
+    ) -> PyResult<(Self, Vec<ReceiverStream<HistoryForReplay>>)> {
+        if buffer_size < 1 {
+            return Err(PyValueError::new_err(
+                "History buffer size must be at least 1",
+            ));
+        }
+        let (txs, streams): (Vec<_>, Vec<_>) = (0..worker_count)
+            .map(|_| {
+                let (tx, rx) = channel(buffer_size);
+                (tx, ReceiverStream::new(rx))
+            })
+            .unzip();
+        Ok((
             Self {
-                tx: Some(tx),
+                txs: Arc::new(Mutex::new(txs)),
+                next_tx: AtomicUsize::new(0),
+                run_ids: Default::default(),
                 runtime,
             },
-            ReceiverStream::new(rx),
-        )
+            streams,
+        ))
+    }
+
+    /// Sender for the next history, picking the replay worker in round-robin
+    /// order
+    fn history_sender(&self) -> PyResult<HistorySender> {
+        let txs = self.txs.lock().unwrap();
+        if txs.is_empty() {
+            return Err(PyRuntimeError::new_err(
+                "Replay worker is no longer accepting new histories",
+            ));
+        }
+        Ok(HistorySender {
+            tx: txs[self.next_tx.fetch_add(1, Ordering::Relaxed) % txs.len()].clone(),
+            run_ids: self.run_ids.clone(),
+        })
+    }
+
+    fn send_history<'p>(
+        &self,
+        py: Python<'p>,
+        workflow_id: &str,
+        history: History,
+        run_id: Option<String>,
+    ) -> PyResult<&'p PyAny> {
+        let sender = self.history_sender()?;
+        let workflow_id = workflow_id.to_string();
+        // We accept this doesn't have logging/tracing
+        self.runtime.future_into_py(py, async move {
+            sender.send(workflow_id, history, run_id).await
+        })
+    }
+
+    /// Offers the history to each worker in round-robin order, starting with
+    /// the one `send_history` would use next
+    fn try_send_history(
+        &self,
+        workflow_id: &str,
+        history: History,
+        run_id: Option<String>,
+    ) -> PyResult<bool> {
+        let txs = self.txs.lock().unwrap();
+        if txs.is_empty() {
+            return Err(PyRuntimeError::new_err(
+                "Replay worker is no longer accepting new histories",
+            ));
+        }
+        let run_ids_entry = run_id_override(run_id, &history);
+        let start = self.next_tx.fetch_add(1, Ordering::Relaxed);
+        let mut item = HistoryForReplay::new(history, workflow_id.to_string());
+        for offset in 0..txs.len() {
+            match txs[(start + offset) % txs.len()].try_send(item) {
+                Ok(()) => {
+                    if let Some((history_run_id, run_id)) = run_ids_entry {
+                        self.run_ids.lock().unwrap().insert(history_run_id, run_id);
+                    }
+                    return Ok(true);
+                }
+                Err(TrySendError::Full(returned)) => item = returned,
+                Err(TrySendError::Closed(_)) => {
+                    return Err(PyRuntimeError::new_err(
+                        "Channel for history replay was dropped, this is an SDK bug.",
+                    ))
+                }
+            }
+        }
+        Ok(false)
+    }
+}
+
+/// Sends a history to one replay worker
+struct HistorySender {
+    tx: Sender<HistoryForReplay>,
+    run_ids: Arc<Mutex<HashMap<String, String>>>,
+}
+
+impl HistorySender {
+    async fn send(
+        self,
+        workflow_id: String,
+        history: History,
+        run_id: Option<String>,
+    ) -> PyResult<()> {
+        if let Some((history_run_id, run_id)) = run_id_override(run_id, &history) {
+            self.run_ids.lock().unwrap().insert(history_run_id, run_id);
+        }
+        self.tx
+            .send(HistoryForReplay::new(history, workflow_id))
+            .await
+            .map_err(|_| {
+                PyRuntimeError::new_err(
+                    "Channel for history replay was dropped, this is an SDK bug.",
+                )
+            })
+    }
+}
+
+/// The given run ID keyed by the one core uses for the history, if they
+/// differ
+fn run_id_override(run_id: Option<String>, history: &History) -> Option<(String, String)> {
+    let history_run_id = history_run_id(history)?;
+    run_id
+        .filter(|run_id| *run_id != history_run_id)
+        .map(|run_id| (history_run_id, run_id))
+}
+
+/// Run ID core uses when replaying the history, from its workflow execution
+/// started event
+fn history_run_id(history: &History) -> Option<String> {
+    match history.events.first()?.attributes.as_ref()? {
+        history_event::Attributes::WorkflowExecutionStartedEventAttributes(attrs)
+            if !attrs.original_execution_run_id.is_empty() =>
+        {
+            Some(attrs.original_execution_run_id.clone())
+        }
+        _ => None,
+    }
+}
+
+/// Async iterator of encoded workflow activations that stops once the worker
+/// has shut down
+#[pyclass]
+pub struct ActivationStream {
+    poller: Poller,
+    outstanding_polls: Arc<OutstandingPolls>,
+    runtime: runtime::Runtime,
+}
+
+#[pymethods]
+impl ActivationStream {
+    fn __aiter__(slf: PyRef<'_, Self>) -> PyRef<'_, Self> {
+        slf
+    }
+
+    fn __anext__(&self, py: Python) -> PyResult<Option<PyObject>> {
+        let poller = self.poller.clone();
+        let poll_guard = self.outstanding_polls.start();
+        let fut = self.runtime.future_into_py(py, async move {
+            poll_guard.resumed().await;
+            let _poll_guard = poll_guard;
+            match poller.poll_workflow_activation().await {
+                Ok((act, _)) => Python::with_gil(|py| encode_to_py_bytes(py, &act)),
+                Err(PollError::ShutDown) => Err(PyStopAsyncIteration::new_err(())),
+                Err(err) => Err(poll_failure(err)),
+            }
+        })?;
+        Ok(Some(fut.into()))
+    }
+}
+
+/// Yields the outcome of each pushed history as core finishes replaying it
+#[pyclass]
+pub struct ReplayResultReceiver {
+    rx: Arc<tokio::sync::Mutex<UnboundedReceiver<ReplayResult>>>,
+    runtime: runtime::Runtime,
+}
+
+#[pymethods]
+impl ReplayResultReceiver {
+    /// Resolves to `(workflow_id, success, error, last_event_id, run_id)`, or
+    /// `None` once the replay worker is gone
+    fn next<'p>(&self, py: Python<'p>) -> PyResult<&'p PyAny> {
+        let rx = self.rx.clone();
+        self.runtime
+            .future_into_py(py, async move { Ok(rx.lock().await.recv().await) })
     }
 }
 
 #[pymethods]
 impl HistoryPusher {
+    /// The run ID, if given, is reported in results instead of the one from
+    /// the history's workflow execution started event
+    #[pyo3(signature = (workflow_id, history_proto, run_id=None))]
     fn push_history<'p>(
         &self,
         py: Python<'p>,
         workflow_id: &str,
         history_proto: &PyBytes,
+        run_id: Option<String>,
     ) -> PyResult<&'p PyAny> {
-        let history = History::decode(history_proto.as_bytes())
-            .map_err(|err| PyValueError::new_err(format!("Invalid proto: {}", err)))?;
-        let wfid = workflow_id.to_string();
-        let tx = if let Some(tx) = self.tx.as_ref() {
-            tx.clone()
-        } else {
-            return Err(PyRuntimeError::new_err(
-                "Replay worker is no longer accepting new histories",
-            ));
-        };
-        // We accept this doesn't have logging/tracing
+        // Copied out so decoding can happen without holding the GIL
+        let bytes = history_proto.as_bytes().to_vec();
+        let history = py
+            .allow_threads(|| History::decode(bytes.as_slice()))
+            .map_err(invalid_proto::<History>)?;
+        self.send_history(py, workflow_id, history, run_id)
+    }
+
+    /// Same as `push_history` but returns false instead of waiting if every
+    /// replay worker's history buffer is full
+    #[pyo3(signature = (workflow_id, history_proto, run_id=None))]
+    fn try_push_history(
+        &self,
+        py: Python,
+        workflow_id: &str,
+        history_proto: &PyBytes,
+        run_id: Option<String>,
+    ) -> PyResult<bool> {
+        let bytes = history_proto.as_bytes().to_vec();
+        let history = py
+            .allow_threads(|| History::decode(bytes.as_slice()))
+            .map_err(invalid_proto::<History>)?;
+        self.try_send_history(workflow_id, history, run_id)
+    }
+
+    /// Fetches the full history of the run through the client, paging through
+    /// `GetWorkflowExecutionHistory`, and pushes it. Without a run ID the
+    /// latest run of the workflow is used. Raises `RPCError` if a fetch fails.
+    #[pyo3(signature = (client, namespace, workflow_id, run_id=None))]
+    fn push_history_from_server<'p>(
+        &self,
+        py: Python<'p>,
+        client: &client::ClientRef,
+        namespace: String,
+        workflow_id: String,
+        run_id: Option<String>,
+    ) -> PyResult<&'p PyAny> {
+        let mut client = client.retry_client.clone();
+        let sender = self.history_sender()?;
         self.runtime.future_into_py(py, async move {
-            tx.send(HistoryForReplay::new(history, wfid))
-                .await
-                .map_err(|_| {
-                    PyRuntimeError::new_err(
-                        "Channel for history replay was dropped, this is an SDK bug.",
-                    )
-                })?;
-            Ok(())
+            let mut history = History::default();
+            let mut next_page_token = Vec::new();
+            loop {
+                let resp = client
+                    .get_workflow_execution_history(tonic::Request::new(
+                        GetWorkflowExecutionHistoryRequest {
+                            namespace: namespace.clone(),
+                            execution: Some(WorkflowExecution {
+                                workflow_id: workflow_id.clone(),
+                                run_id: run_id.clone().unwrap_or_default(),
+                            }),
+                            next_page_token,
+                            ..Default::default()
+                        },
+                    ))
+                    .await
+                    .map_err(client::rpc_error)?
+                    .into_inner();
+                if let Some(page) = resp.history {
+                    history.events.extend(page.events);
+                }
+                if resp.next_page_token.is_empty() {
+                    break;
+                }
+                next_page_token = resp.next_page_token;
+            }
+            sender.send(workflow_id, history, run_id).await
         })
     }
 
     fn close(&mut self) {
-        self.tx.take();
+        self.txs.lock().unwrap().clear();
     }
 }
@@ -5,11 +5,18 @@ Nothing in this module should be considered stable. The API may change.
 
 from __future__ import annotations
 
+import asyncio
+import os
 from dataclasses import dataclass
+from datetime import timedelta
+from pathlib import Path
 from typing import (
     TYPE_CHECKING,
+    Any,
+    AsyncIterator,
     Awaitable,
     Callable,
+    Dict,
     List,
     Optional,
     Sequence,
@@ -19,6 +26,8 @@ from typing import (
 )
 
 import google.protobuf.internal.containers
+import google.protobuf.json_format
+import google.protobuf.message
 from typing_extensions import TypeAlias
 
 import temporalio.api.common.v1
@@ -30,12 +39,23 @@ import temporalio.bridge.proto.workflow_activation
 import temporalio.bridge.proto.workflow_completion
 import temporalio.bridge.runtime
 import temporalio.bridge.temporal_sdk_bridge
+import temporalio.client
 import temporalio.converter
 import temporalio.exceptions
 from temporalio.bridge.temporal_sdk_bridge import (
     CustomSlotSupplier as BridgeCustomSlotSupplier,
 )
-from temporalio.bridge.temporal_sdk_bridge import PollShutdownError
+from temporalio.bridge.temporal_sdk_bridge import (
+    ManualSlotSupplier as BridgeManualSlotSupplier,
+)
+from temporalio.bridge.temporal_sdk_bridge import (
+    CompletionError,
+    InvalidProtoError,
+    PollCanceller,
+    PollInternalError,
+    PollShutdownError,
+    PollTransportError,
+)
 
 
 @dataclass
@@ -47,20 +67,58 @@ class WorkerConfig:
     build_id: str
     identity_override: Optional[str]
     max_cached_workflows: int
+    disable_sticky_queues: bool
     tuner: TunerHolder
-    max_concurrent_workflow_task_polls: int
+    workflow_task_poller_behavior: PollerBehavior
     nonsticky_to_sticky_poll_ratio: float
-    max_concurrent_activity_task_polls: int
+    activity_task_poller_behavior: PollerBehavior
     no_remote_activities: bool
     sticky_queue_schedule_to_start_timeout_millis: int
     max_heartbeat_throttle_interval_millis: int
     default_heartbeat_throttle_interval_millis: int
+    local_timeout_buffer_for_activities_millis: int
     max_activities_per_second: Optional[float]
     max_task_queue_activities_per_second: Optional[float]
-    graceful_shutdown_period_millis: int
+    max_local_activities_per_second: Optional[float]
+    graceful_shutdown_period_millis: Optional[int]
     use_worker_versioning: bool
-    nondeterminism_as_workflow_fail: bool
-    nondeterminism_as_workflow_fail_for_types: Set[str]
+    deployment_options: Optional[WorkerDeploymentOptions]
+    workflow_failure_errors: Set[str]
+    workflow_types_to_failure_errors: Dict[str, Set[str]]
+    completion_interceptor: Optional[Callable[[bytes], bytes]]
+    emit_slot_events: bool
+    poll_watchdog: bool
+
+
+@dataclass
+class PollerBehaviorSimpleMaximum:
+    """Python representation of the Rust struct for simple poller behavior."""
+
+    maximum: int
+
+
+@dataclass
+class PollerBehaviorAutoscaling:
+    """Python representation of the Rust struct for autoscaling poller behavior."""
+
+    minimum: int
+    maximum: int
+    initial: int
+
+
+PollerBehavior: TypeAlias = Union[
+    PollerBehaviorSimpleMaximum,
+    PollerBehaviorAutoscaling,
+]
+
+
+@dataclass
+class WorkerDeploymentOptions:
+    """Python representation of the Rust struct for worker deployment options."""
+
+    deployment_name: str
+    build_id: str
+    default_versioning_behavior: int
 
 
 @dataclass
@@ -69,6 +127,14 @@ class ResourceBasedTunerConfig:
 
     target_memory_usage: float
     target_cpu_usage: float
+    memory_p_gain: Optional[float]
+    memory_i_gain: Optional[float]
+    memory_d_gain: Optional[float]
+    memory_output_threshold: Optional[float]
+    cpu_p_gain: Optional[float]
+    cpu_i_gain: Optional[float]
+    cpu_d_gain: Optional[float]
+    cpu_output_threshold: Optional[float]
 
 
 @dataclass
@@ -92,6 +158,7 @@ SlotSupplier: TypeAlias = Union[
     FixedSizeSlotSupplier,
     ResourceBasedSlotSupplier,
     BridgeCustomSlotSupplier,
+    BridgeManualSlotSupplier,
 ]
 
 
@@ -116,28 +183,216 @@ class Worker:
             )
         )
 
+    @staticmethod
+    def dry_run(client: temporalio.bridge.client.Client, config: WorkerConfig) -> None:
+        """Validate a worker config the same way :py:meth:`create` does, without
+        creating a worker. Nothing is polled or sent to the server.
+
+        Raises:
+            ValueError: The config is invalid.
+        """
+        temporalio.bridge.temporal_sdk_bridge.new_worker_dry_run(
+            client._runtime._ref, client._ref, config
+        )
+
     @staticmethod
     def for_replay(
         runtime: temporalio.bridge.runtime.Runtime,
         config: WorkerConfig,
-    ) -> Tuple[Worker, temporalio.bridge.temporal_sdk_bridge.HistoryPusher]:
-        """Create a bridge replay worker."""
+        history_buffer_size: int = 1,
+    ) -> Tuple[
+        Worker,
+        HistoryPusher,
+        temporalio.bridge.temporal_sdk_bridge.ReplayResultReceiver,
+    ]:
+        """Create a bridge replay worker.
+
+        ``history_buffer_size`` is how many pushed histories may be queued
+        before pushing waits on core. Must be at least 1.
+
+        The returned receiver's ``next()`` resolves to a
+        ``(workflow_id, success, error, last_event_id, run_id)`` tuple as each
+        pushed history finishes replaying, or ``None`` once the replay worker is
+        gone. ``last_event_id`` is the ID of the last history event given to the
+        workflow before it finished or failed. ``run_id`` is the one given to
+        the pusher when pushing the history, or otherwise the one from the
+        history's workflow execution started event.
+        """
         [
             replay_worker,
             pusher,
+            result_receiver,
         ] = temporalio.bridge.temporal_sdk_bridge.new_replay_worker(
-            runtime._ref, config
+            runtime._ref, config, history_buffer_size
+        )
+        return Worker(replay_worker), HistoryPusher(pusher), result_receiver
+
+    @staticmethod
+    def for_replay_pool(
+        runtime: temporalio.bridge.runtime.Runtime,
+        config: WorkerConfig,
+        concurrency: int,
+        history_buffer_size: int = 1,
+    ) -> Tuple[
+        List[Worker],
+        HistoryPusher,
+        temporalio.bridge.temporal_sdk_bridge.ReplayResultReceiver,
+    ]:
+        """Create ``concurrency`` bridge replay workers.
+
+        This is the same as :py:meth:`for_replay` except the single returned
+        pusher round-robins histories across the workers and the single
+        returned receiver yields results from all of them. Closing the pusher
+        closes it for every worker.
+        """
+        [
+            replay_workers,
+            pusher,
+            result_receiver,
+        ] = temporalio.bridge.temporal_sdk_bridge.new_replay_worker_pool(
+            runtime._ref, config, concurrency, history_buffer_size
+        )
+        return (
+            [Worker(w) for w in replay_workers],
+            HistoryPusher(pusher),
+            result_receiver,
         )
-        return Worker(replay_worker), pusher
 
     def __init__(self, ref: temporalio.bridge.temporal_sdk_bridge.WorkerRef) -> None:
         """Create SDK core worker from a bridge worker."""
         self._ref = ref
 
+    @property
+    def namespace(self) -> str:
+        """Namespace the worker was created with."""
+        return self._ref.namespace
+
+    @property
+    def task_queue(self) -> str:
+        """Task queue the worker was created with."""
+        return self._ref.task_queue
+
+    @property
+    def build_id(self) -> str:
+        """Build ID of the deployment version if set, otherwise the build ID
+        the worker was created with.
+        """
+        return self._ref.build_id
+
+    def set_build_id(self, build_id: str) -> None:
+        """Set the worker's build ID.
+
+        Core does not support changing the build ID after the worker is
+        created, so this only accepts the current build ID.
+
+        Raises:
+            RuntimeError: The build ID differs from the current one.
+        """
+        self._ref.set_build_id(build_id)
+
     async def validate(self) -> None:
         """Validate the bridge worker."""
         await self._ref.validate()
 
+    async def validate_detailed(
+        self,
+    ) -> temporalio.bridge.temporal_sdk_bridge.WorkerValidation:
+        """Validate the bridge worker, returning details instead of raising on
+        validation failure.
+        """
+        return await self._ref.validate_detailed()
+
+    async def wait_ready(self, timeout: timedelta) -> bool:
+        """Wait for the worker to validate against the server.
+
+        Unlike :py:meth:`validate`, this may be called repeatedly.
+
+        Returns:
+            True once validation succeeds, False if it has not within the
+            timeout.
+        """
+        return await self._ref.wait_ready(round(timeout.total_seconds() * 1000))
+
+    async def ping_server(self) -> float:
+        """Make a lightweight call to the server through the worker's client.
+
+        Returns:
+            Round-trip time of the call in milliseconds.
+        """
+        return await self._ref.ping_server()
+
+    def server_capabilities(self) -> Optional[Dict[str, bool]]:
+        """Capability flags the server reported to the worker's client.
+
+        Returns:
+            Flags keyed by their ``GetSystemInfoResponse.Capabilities`` field
+            name, or ``None`` if the client has not obtained them.
+        """
+        return self._ref.server_capabilities()
+
+    def effective_tuner_config(self) -> Dict[str, Dict[str, Any]]:
+        """Slot supplier configuration as given to core.
+
+        Returns:
+            Dict keyed by ``workflow``, ``activity`` and ``local_activity``,
+            each with a ``kind`` of ``fixed_size`` (with ``num_slots``),
+            ``resource_based`` (with ``minimum_slots``, ``maximum_slots``,
+            ``ramp_throttle_millis``, ``target_memory_usage``,
+            ``target_cpu_usage`` and the PID controller gains and output
+            thresholds, ``None`` where core's default is used), ``custom`` or
+            ``manual``. The ``local_activity`` entry also has
+            ``max_per_second``, ``None`` if local activities are not rate
+            limited.
+        """
+        return self._ref.effective_tuner_config()
+
+    def effective_poller_behaviors(self) -> Dict[str, Optional[Dict[str, Any]]]:
+        """Poller behaviors as given to core.
+
+        Returns:
+            Dict keyed by ``workflow`` and ``activity``, each with a ``kind`` of
+            ``simple_maximum`` (with ``maximum``) or ``autoscaling``
+            (with ``minimum``, ``maximum`` and ``initial``). The ``activity``
+            entry is ``None`` if the worker never polls for activities.
+        """
+        return self._ref.effective_poller_behaviors()
+
+    async def describe_task_queue(
+        self, task_queue_type: str = "workflow"
+    ) -> Dict[str, Any]:
+        """Describe the worker's task queue through the worker's client.
+
+        Args:
+            task_queue_type: Either ``"workflow"`` or ``"activity"``.
+
+        Returns:
+            Dict with ``backlog_count``, ``tasks_add_rate``,
+            ``tasks_dispatch_rate`` and ``pollers``, a list of dicts with each
+            poller's ``identity`` and ``rate_per_second``.
+        """
+        return await self._ref.describe_task_queue(task_queue_type)
+
+    async def run_replay_all(
+        self, histories: Sequence[Tuple[str, temporalio.api.history.v1.History]]
+    ) -> Dict[str, Any]:
+        """Replay all of the given ``(workflow_id, history)`` pairs.
+
+        This pushes every history, closes the history pusher, and waits for
+        every result. Activations must still be polled and completed while
+        this runs. Results collected here are not also given to the result
+        receiver.
+
+        Returns:
+            Summary with ``total``, ``succeeded`` and ``failed`` counts and
+            ``failures`` as a list of ``(workflow_id, error)`` tuples.
+        """
+        return await self._ref.run_replay_all(
+            [
+                (workflow_id, history.SerializeToString())
+                for workflow_id, history in histories
+            ]
+        )
+
     async def poll_workflow_activation(
         self,
     ) -> temporalio.bridge.proto.workflow_activation.WorkflowActivation:
@@ -148,6 +403,42 @@ class Worker:
             )
         )
 
+    async def poll_workflow_activation_with_run_id(self) -> Tuple[str, bytes]:
+        """Poll for a workflow activation, returning its run ID and raw bytes."""
+        return await self._ref.poll_workflow_activation_with_run_id()
+
+    async def poll_workflow_activation_with_meta(
+        self,
+    ) -> Tuple[
+        temporalio.bridge.proto.workflow_activation.WorkflowActivation,
+        Optional[bool],
+        bool,
+    ]:
+        """Poll for a workflow activation, also returning whether it was polled
+        from the sticky queue and whether its run was already in the worker's
+        cache.
+
+        Core does not report which task queue an activation was polled from, so
+        ``is_sticky`` is always ``None``, meaning unknown.
+        """
+        b, is_sticky, from_cache = await self._ref.poll_workflow_activation_with_meta()
+        return (
+            temporalio.bridge.proto.workflow_activation.WorkflowActivation.FromString(
+                b
+            ),
+            is_sticky,
+            from_cache,
+        )
+
+    async def activation_stream(
+        self,
+    ) -> AsyncIterator[temporalio.bridge.proto.workflow_activation.WorkflowActivation]:
+        """Iterate workflow activations until the worker shuts down."""
+        async for b in self._ref.activation_stream():
+            yield temporalio.bridge.proto.workflow_activation.WorkflowActivation.FromString(
+                b
+            )
+
     async def poll_activity_task(
         self,
     ) -> temporalio.bridge.proto.activity_task.ActivityTask:
@@ -156,46 +447,562 @@ class Worker:
             await self._ref.poll_activity_task()
         )
 
+    async def poll_activity_task_with_type(self) -> Tuple[str, bytes, bytes]:
+        """Poll for an activity task, returning its activity type and task
+        token alongside the still-serialized task.
+
+        The activity type is empty for cancel tasks.
+        """
+        return await self._ref.poll_activity_task_with_type()
+
+    async def poll_any(
+        self,
+    ) -> Tuple[
+        str,
+        Union[
+            temporalio.bridge.proto.workflow_activation.WorkflowActivation,
+            temporalio.bridge.proto.activity_task.ActivityTask,
+        ],
+    ]:
+        """Poll for a workflow activation and an activity task at once.
+
+        Returns:
+            ``("workflow", activation)`` or ``("activity", task)`` for whichever
+            arrives first. The other poll is resumed by the next call.
+
+        Raises:
+            PollShutdownError: Both polls have seen shutdown.
+        """
+        kind, b = await self._ref.poll_any()
+        if kind == "workflow":
+            return (
+                kind,
+                temporalio.bridge.proto.workflow_activation.WorkflowActivation.FromString(
+                    b
+                ),
+            )
+        return kind, temporalio.bridge.proto.activity_task.ActivityTask.FromString(b)
+
+    async def poll_workflow_activation_opt(
+        self,
+    ) -> Optional[temporalio.bridge.proto.workflow_activation.WorkflowActivation]:
+        """Poll for a workflow activation, returning None on shutdown."""
+        b = await self._ref.poll_workflow_activation_opt()
+        if b is None:
+            return None
+        return temporalio.bridge.proto.workflow_activation.WorkflowActivation.FromString(
+            b
+        )
+
+    async def poll_activity_task_opt(
+        self,
+    ) -> Optional[temporalio.bridge.proto.activity_task.ActivityTask]:
+        """Poll for an activity task, returning None on shutdown."""
+        b = await self._ref.poll_activity_task_opt()
+        if b is None:
+            return None
+        return temporalio.bridge.proto.activity_task.ActivityTask.FromString(b)
+
+    def new_poll_canceller(self) -> PollCanceller:
+        """Create a canceller for use with cancellable polls."""
+        return self._ref.new_poll_canceller()
+
+    async def poll_activity_task_timeout(
+        self, timeout: timedelta
+    ) -> Optional[temporalio.bridge.proto.activity_task.ActivityTask]:
+        """Poll for an activity task, returning None if none arrives in time.
+
+        A timeout does not shut down the worker and polling can be resumed. The
+        in-flight poll is kept and resumed by the next activity poll, so a task
+        already taken from core is not lost.
+        """
+        b = await self._ref.poll_activity_task_timeout(
+            round(timeout.total_seconds() * 1000)
+        )
+        if b is None:
+            return None
+        return temporalio.bridge.proto.activity_task.ActivityTask.FromString(b)
+
+    async def poll_workflow_activation_cancellable(
+        self, canceller: PollCanceller
+    ) -> Optional[temporalio.bridge.proto.workflow_activation.WorkflowActivation]:
+        """Poll for a workflow activation, returning None if cancelled.
+
+        Cancelling does not shut down the worker. The in-flight poll is kept
+        and resumed by the next workflow poll, so an activation already taken
+        from core is not lost. Reset the canceller to poll again.
+        """
+        b = await self._ref.poll_workflow_activation_cancellable(canceller)
+        if b is None:
+            return None
+        return temporalio.bridge.proto.workflow_activation.WorkflowActivation.FromString(
+            b
+        )
+
     async def complete_workflow_activation(
         self,
         comp: temporalio.bridge.proto.workflow_completion.WorkflowActivationCompletion,
     ) -> None:
-        """Complete a workflow activation."""
+        """Complete a workflow activation.
+
+        Failures raise ``CompletionError``, whose ``code`` is the gRPC status
+        code when the server rejected the completion and ``None`` otherwise.
+        """
         await self._ref.complete_workflow_activation(comp.SerializeToString())
 
+    async def complete_workflow_activation_json(self, json: str) -> None:
+        """Complete a workflow activation given as JSON.
+
+        This is much slower than :py:meth:`complete_workflow_activation` and is
+        only meant for tooling such as crafting completions by hand. Failures
+        raise the same errors, plus ``ValueError`` for invalid JSON.
+        """
+        await self._ref.complete_workflow_activation_json(json)
+
+    async def complete_workflow_activation_commands(
+        self, run_id: str, commands: Sequence[bytes]
+    ) -> None:
+        """Successfully complete a workflow activation from already serialized
+        ``WorkflowCommand`` protos.
+
+        This is an advanced alternative to :py:meth:`complete_workflow_activation`
+        that avoids serializing the whole completion. Failures raise the same
+        errors.
+        """
+        await self._ref.complete_workflow_activation_commands(run_id, list(commands))
+
     async def complete_activity_task(
         self, comp: temporalio.bridge.proto.ActivityTaskCompletion
     ) -> None:
-        """Complete an activity task."""
+        """Complete an activity task.
+
+        Failures raise ``CompletionError``, whose ``code`` is the gRPC status
+        code when the server rejected the completion and ``None`` otherwise.
+        """
         await self._ref.complete_activity_task(comp.SerializeToString())
 
+    async def complete_activity_task_failed(
+        self,
+        task_token: bytes,
+        message: str,
+        *,
+        failure_type: Optional[str] = None,
+        details: Optional[temporalio.api.common.v1.Payloads] = None,
+    ) -> None:
+        """Fail an activity task with an application failure.
+
+        This is a shortcut for :py:meth:`complete_activity_task` with a failed
+        result, so failures raise the same errors.
+        """
+        await self._ref.complete_activity_task_failed(
+            task_token,
+            message,
+            failure_type,
+            details.SerializeToString() if details else None,
+        )
+
+    async def complete_activity_task_cancelled(
+        self,
+        task_token: bytes,
+        *,
+        details: Optional[temporalio.api.common.v1.Payloads] = None,
+    ) -> None:
+        """Complete an activity task as cancelled, acknowledging a cancel
+        request.
+
+        This is a shortcut for :py:meth:`complete_activity_task` with a
+        cancelled result, so failures raise the same errors.
+        """
+        await self._ref.complete_activity_task_cancelled(
+            task_token,
+            details.SerializeToString() if details else None,
+        )
+
+    async def complete_workflow_activations(
+        self,
+        comps: Sequence[
+            temporalio.bridge.proto.workflow_completion.WorkflowActivationCompletion
+        ],
+    ) -> List[Optional[Exception]]:
+        """Complete multiple workflow activations, concurrently across runs
+        but in the given order within a run.
+
+        Returns:
+            For each completion in order, ``None`` if it succeeded or the
+            exception :py:meth:`complete_workflow_activation` would have raised
+            for it.
+        """
+        return await self._ref.complete_workflow_activations(
+            [comp.SerializeToString() for comp in comps]
+        )
+
+    async def complete_activity_tasks(
+        self, comps: Sequence[temporalio.bridge.proto.ActivityTaskCompletion]
+    ) -> List[Optional[Exception]]:
+        """Complete multiple activity tasks concurrently.
+
+        Returns:
+            For each completion in order, ``None`` if it succeeded or the
+            exception :py:meth:`complete_activity_task` would have raised for
+            it.
+        """
+        return await self._ref.complete_activity_tasks(
+            [comp.SerializeToString() for comp in comps]
+        )
+
     def record_activity_heartbeat(
         self, comp: temporalio.bridge.proto.ActivityHeartbeat
     ) -> None:
         """Record an activity heartbeat."""
         self._ref.record_activity_heartbeat(comp.SerializeToString())
 
-    def request_workflow_eviction(self, run_id: str) -> None:
-        """Request a workflow be evicted."""
-        self._ref.request_workflow_eviction(run_id)
+    def request_workflow_eviction(
+        self,
+        run_id: str,
+        reason: Optional[str] = None,
+        message: Optional[str] = None,
+    ) -> None:
+        """Request a workflow be evicted.
+
+        The reason, if given, must be an eviction reason name such as
+        ``LANG_REQUESTED``. The reason and message are logged with the request.
+        """
+        self._ref.request_workflow_eviction(run_id, reason, message)
+
+    def cached_workflow_count(self) -> int:
+        """Number of workflows currently in the worker's cache."""
+        return self._ref.cached_workflow_count()
+
+    def effective_identity(self) -> Optional[str]:
+        """Identity the worker reports to the server, or None for replay
+        workers without an identity override.
+        """
+        return self._ref.effective_identity()
+
+    async def await_eviction(self, run_id: str, timeout: timedelta) -> bool:
+        """Wait for an eviction of the run to be polled.
+
+        Only the last 1000 evicted runs are remembered.
+
+        Returns:
+            True once the run is evicted, or immediately if it was recently
+            evicted. False if it is not evicted within the timeout.
+
+        Raises:
+            ValueError: The run is neither cached nor recently evicted by this
+                worker.
+        """
+        return await self._ref.await_eviction(
+            run_id, round(timeout.total_seconds() * 1000)
+        )
+
+    def slot_event_receiver(
+        self,
+    ) -> temporalio.bridge.temporal_sdk_bridge.SlotEventReceiver:
+        """Receiver of slot events, if enabled via ``emit_slot_events``.
+
+        The receiver's ``next()`` resolves to a
+        ``(slot_type, event, issued_count)`` tuple for every slot reserved,
+        marked used or released, where ``event`` is ``"reserved"``, ``"used"``
+        or ``"released"`` and ``issued_count`` is how many slots of that type
+        are issued after it. Up to 1024 unread events are buffered, after
+        which new events are dropped and counted in the receiver's
+        ``dropped``.
+
+        Raises:
+            RuntimeError: Slot events were not enabled.
+        """
+        return self._ref.slot_event_receiver()
+
+    def evict_all_cached_workflows(self) -> int:
+        """Request eviction of every cached workflow.
+
+        Evictions are delivered as activations that must still be polled and
+        completed.
+
+        Returns:
+            Number of workflows eviction was requested for.
+        """
+        return self._ref.evict_all_cached_workflows()
+
+    def cached_run_ids(self) -> List[str]:
+        """Run IDs of workflows currently in the worker's cache."""
+        return self._ref.cached_run_ids()
+
+    def set_completion_timing_callback(
+        self, callback: Optional[Callable[[str, float], None]]
+    ) -> None:
+        """Set or clear a callback invoked with the run ID and milliseconds
+        between polling a workflow activation and successfully completing it.
+
+        Only activations polled while a callback is set are timed. The callback
+        is invoked from a Core thread.
+        """
+        self._ref.set_completion_timing_callback(callback)
+
+    def poll_stats(self) -> Dict[str, int]:
+        """Counts of workflow activations and activity tasks polled and
+        completed by this worker, and of polls that saw shutdown.
+        """
+        return self._ref.poll_stats()
+
+    def seconds_since_last_poll(self) -> Optional[float]:
+        """Seconds since a poll last returned a task, or since the worker was
+        created if none has yet, if enabled via ``poll_watchdog``.
+
+        A value that keeps growing while work is queued signals a stuck worker.
+        """
+        return self._ref.seconds_since_last_poll()
+
+    def get_metrics_snapshot(self) -> Dict[str, Union[int, float]]:
+        """Latest buffered metric values for this worker's task queue.
+
+        Core does not attribute metrics to individual workers, so workers on
+        the same namespace and task queue share one snapshot and its counters
+        are totals across them. The snapshot is kept until the last of those
+        workers has shut down.
+
+        Reading a snapshot drains the runtime's metric buffer, keeping the
+        drained updates for the next retrieval. Empty if the runtime is not
+        configured with a metric buffer or once the worker has shut down.
+        """
+        return self._ref.get_metrics_snapshot()
+
+    def replace_client(
+        self,
+        client: temporalio.bridge.client.Client,
+        namespace: str,
+        *,
+        force: bool = False,
+    ) -> None:
+        """Replace the worker client.
+
+        Bridge clients are not bound to a namespace, so ``namespace`` is the
+        one the new client is used with.
 
-    def replace_client(self, client: temporalio.bridge.client.Client) -> None:
-        """Replace the worker client."""
-        self._ref.replace_client(client._ref)
+        Raises:
+            ValueError: The namespace does not match the worker's, or the
+                worker has no identity override and the new client's identity
+                does not match the current client's. Not raised if ``force``
+                is set.
+        """
+        self._ref.replace_client(client._ref, namespace, force)
 
     def initiate_shutdown(self) -> None:
         """Start shutdown of the worker."""
         self._ref.initiate_shutdown()
 
-    async def finalize_shutdown(self) -> None:
+    def pause(self) -> None:
+        """Make polls started from now on wait until :py:meth:`resume` is
+        called or shutdown is initiated. Polls already in flight and cached
+        workflows are unaffected.
+        """
+        self._ref.pause()
+
+    def resume(self) -> None:
+        """Let paused and future polls proceed."""
+        self._ref.resume()
+
+    def is_paused(self) -> bool:
+        """Whether polling is paused."""
+        return self._ref.is_paused()
+
+    def shutdown_phase(self) -> str:
+        """Shutdown phase as seen by the bridge.
+
+        One of ``running``, ``initiated``, ``draining`` once a poll has seen
+        core stop handing out tasks, or ``complete`` once core's shutdown,
+        including in-flight tasks, has finished.
+        """
+        return "complete" if self._ref is None else self._ref.shutdown_phase()
+
+    def is_shutdown(self) -> bool:
+        """Whether core's shutdown, including in-flight tasks, has finished or
+        the worker has been finalized.
+        """
+        return self._ref is None or self._ref.is_shutdown()
+
+    async def initiate_shutdown_with_deadline(self, deadline: timedelta) -> bool:
+        """Start shutdown of the worker, evicting all cached workflows if it
+        has not drained by the deadline.
+
+        Outstanding activity slots are not abandoned at the deadline because
+        core cannot release them, so activities are still only bounded by the
+        graceful shutdown timeout.
+
+        Returns:
+            True if the worker drained before the deadline, False if the
+            deadline was reached first.
+        """
+        return await self._ref.initiate_shutdown_with_deadline(
+            round(deadline.total_seconds() * 1000)
+        )
+
+    async def wait_all_polls_drained(self) -> None:
+        """Wait until shutdown has been initiated and no polls are in flight.
+
+        This can complete well before outstanding tasks finish.
+        """
+        await self._ref.wait_all_polls_drained()
+
+    async def finalize_shutdown(self, timeout: Optional[timedelta] = None) -> bool:
         """Finalize the worker.
 
         This will fail if shutdown hasn't completed fully due to internal
         reference count checks.
+
+        Returns:
+            True if finalization completed, False if the timeout was reached
+            first.
         """
         ref = self._ref
         self._ref = None
-        await ref.finalize_shutdown()
+        timeout_millis = (
+            round(timeout.total_seconds() * 1000) if timeout is not None else None
+        )
+        return await ref.finalize_shutdown(timeout_millis)
+
+    async def finalize_shutdown_forced(self, timeout: timedelta) -> None:
+        """Finalize the worker, first waiting up to the timeout for in-flight
+        calls to release the worker.
+
+        Unlike :py:meth:`finalize_shutdown`, this does not fail immediately if
+        a straggling poll or completion still references the worker. Shutdown
+        must already have been initiated. If the timeout is reached, this
+        raises and the worker is left as is.
+        """
+        await self._ref.wait_worker_released(round(timeout.total_seconds() * 1000))
+        await self.finalize_shutdown()
+
+
+class HistoryPusher:
+    """Pushes histories to bridge replay workers."""
+
+    def __init__(
+        self, ref: temporalio.bridge.temporal_sdk_bridge.HistoryPusher
+    ) -> None:
+        """Create a history pusher from a bridge history pusher."""
+        self._ref = ref
+
+    async def push_history(
+        self, workflow_id: str, history_proto: bytes, run_id: Optional[str] = None
+    ) -> None:
+        """Push a serialized ``History``, waiting while the replay workers'
+        history buffers are full.
+
+        The run ID, if given, is reported in results instead of the one from
+        the history's workflow execution started event.
+        """
+        await self._ref.push_history(workflow_id, history_proto, run_id)
+
+    def try_push_history(
+        self, workflow_id: str, history_proto: bytes, run_id: Optional[str] = None
+    ) -> bool:
+        """Same as :py:meth:`push_history` but returns False instead of waiting
+        if every replay worker's history buffer is full.
+        """
+        return self._ref.try_push_history(workflow_id, history_proto, run_id)
+
+    async def push_history_from_server(
+        self,
+        client: temporalio.bridge.client.Client,
+        namespace: str,
+        workflow_id: str,
+        run_id: Optional[str] = None,
+    ) -> None:
+        """Fetch the full history of the run through the client and push it.
+
+        Without a run ID the latest run of the workflow is used. A given run ID
+        is reported in results as in :py:meth:`push_history`. Raises
+        ``RPCError`` if a fetch fails.
+        """
+        await self._ref.push_history_from_server(
+            client._ref, namespace, workflow_id, run_id
+        )
+
+    async def push_history_json(
+        self, workflow_id: str, history_json: str, run_id: Optional[str] = None
+    ) -> None:
+        """Push a history given as JSON.
+
+        Both the proto3 JSON mapping, as produced by
+        :py:meth:`temporalio.client.WorkflowHistory.to_json`, and the JSON
+        exported by the Temporal UI and CLI are accepted. The run ID is used as
+        in :py:meth:`push_history`.
+
+        Raises:
+            ValueError: The JSON is not a valid history.
+        """
+        await self.push_history(
+            workflow_id, _history_json_to_proto(history_json), run_id
+        )
+
+    async def push_history_file(
+        self,
+        workflow_id: str,
+        path: Union[str, os.PathLike[str]],
+        run_id: Optional[str] = None,
+    ) -> None:
+        """Push a history read from a file, without blocking the event loop.
+
+        Files ending in ``.json`` are parsed as JSON, as in
+        :py:meth:`push_history_json`, and files ending in ``.pb`` or ``.binpb``
+        as protobuf. Otherwise JSON is assumed if the content starts with
+        ``{``. If the assumed format fails to parse, the other is tried. The
+        run ID is used as in :py:meth:`push_history`.
+
+        Raises:
+            ValueError: The file is neither a JSON nor a protobuf history.
+        """
+        history_proto = await asyncio.to_thread(_read_history_file, Path(path))
+        await self.push_history(workflow_id, history_proto, run_id)
+
+    def close(self) -> None:
+        """Stop accepting histories. Replay workers shut down once the histories
+        already pushed have been replayed.
+        """
+        self._ref.close()
+
+
+def _history_json_to_proto(history_json: Union[str, bytes]) -> bytes:
+    if isinstance(history_json, bytes):
+        history_json = history_json.decode()
+    try:
+        # Handles the UI and CLI enum spellings, not just the proto3 mapping
+        history = temporalio.client.WorkflowHistory.from_json("", history_json)
+    except (ValueError, google.protobuf.json_format.ParseError) as err:
+        raise ValueError(f"Invalid history JSON: {err}") from err
+    return temporalio.api.history.v1.History(events=history.events).SerializeToString()
+
+
+def _history_proto(history_proto: bytes) -> bytes:
+    try:
+        temporalio.api.history.v1.History.FromString(history_proto)
+    except google.protobuf.message.DecodeError as err:
+        raise ValueError(f"Invalid history proto: {err}") from err
+    return history_proto
+
+
+def _read_history_file(path: Path) -> bytes:
+    content = path.read_bytes()
+    if path.suffix == ".json":
+        is_json = True
+    elif path.suffix in (".pb", ".binpb"):
+        is_json = False
+    else:
+        is_json = content.lstrip().startswith(b"{")
+    parsers: List[Callable[[bytes], bytes]] = [_history_json_to_proto, _history_proto]
+    if not is_json:
+        parsers.reverse()
+    try:
+        return parsers[0](content)
+    except ValueError as err:
+        try:
+            return parsers[1](content)
+        except ValueError:
+            raise ValueError(
+                f"Unable to parse history file {path} as protobuf or JSON: {err}"
+            ) from err
 
 
 # See https://mypy.readthedocs.io/en/stable/runtime_troubles.html#using-classes-that-are-generic-in-stubs-but-not-at-runtime
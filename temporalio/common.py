@@ -167,6 +167,21 @@ class QueryRejectCondition(IntEnum):
     )
 
 
+class VersioningBehavior(IntEnum):
+    """How a workflow may move between worker deployment versions.
+
+    .. warning::
+        Worker deployment based versioning is currently experimental.
+    """
+
+    UNSPECIFIED = 0
+    """Workflow versioning behavior is not specified."""
+    PINNED = 1
+    """Workflow stays on the deployment version it started on."""
+    AUTO_UPGRADE = 2
+    """Workflow moves to the current deployment version on its next task."""
+
+
 @dataclass(frozen=True)
 class RawValue:
     """Representation of an unconverted, raw payload.
@@ -70,13 +70,20 @@ class Runtime:
             raise RuntimeError("Runtime default already set")
         _default_runtime = runtime
 
-    def __init__(self, *, telemetry: TelemetryConfig) -> None:
+    def __init__(
+        self, *, telemetry: TelemetryConfig, worker_threads: Optional[int] = None
+    ) -> None:
         """Create a default runtime with the given telemetry config.
 
         Each new runtime creates a new internal thread pool, so use sparingly.
+
+        Args:
+            telemetry: Telemetry configuration.
+            worker_threads: Number of threads in the runtime's internal thread
+                pool. Must be at least 1. Default is the number of CPUs.
         """
         self._core_runtime = temporalio.bridge.runtime.Runtime(
-            telemetry=telemetry._to_bridge_config()
+            telemetry=telemetry._to_bridge_config(), worker_threads=worker_threads
         )
         if isinstance(telemetry.metrics, MetricBuffer):
             telemetry.metrics._runtime = self
@@ -138,6 +145,14 @@ class LoggingConfig:
             if isinstance(self.filter, str)
             else self.filter.formatted(),
             forward_to=None if not self.forwarding else self.forwarding._on_logs,
+            forward_max_batch_size=(
+                None if not self.forwarding else self.forwarding.max_batch_size
+            ),
+            forward_max_batch_interval_millis=(
+                None
+                if not self.forwarding
+                else int(self.forwarding.max_batch_interval.total_seconds() * 1000)
+            ),
         )
 
 
@@ -159,8 +174,9 @@ class LogForwardingConfig:
     with Python-originated log messages by a few milliseconds.
 
     If for some reason lots of logs occur within the buffered time (i.e.
-    thousands), they may be sent earlier. Users are discouraged from using this
-    with ``TRACE`` Core logging.
+    thousands), they may be sent earlier. The buffering can be tuned with
+    ``max_batch_size`` and ``max_batch_interval``. Users are discouraged from
+    using this with ``TRACE`` Core logging.
 
     All log records produced have a ``temporal_log`` attribute that contains a
     representation of the Core log. This representation has a ``fields``
@@ -186,6 +202,13 @@ class LogForwardingConfig:
     """If true, the default, the extra fields dict is appended to the
     message."""
 
+    max_batch_size: int = 2048
+    """Most Core logs that are buffered before being sent to Python at once.
+    Must be at least 1."""
+
+    max_batch_interval: timedelta = timedelta(milliseconds=10)
+    """Longest time Core logs are buffered before being sent to Python."""
+
     def _on_logs(
         self, logs: Sequence[temporalio.bridge.runtime.BufferedLogEntry]
     ) -> None:
@@ -342,9 +365,7 @@ class MetricBuffer:
         """
         if not self._runtime:
             raise RuntimeError("Attempting to retrieve updates before runtime created")
-        return self._runtime._core_runtime.retrieve_buffered_metrics(
-            self._durations_as_seconds
-        )
+        return self._runtime._core_runtime.retrieve_buffered_metrics()
 
 
 @dataclass(frozen=True)
@@ -382,6 +403,8 @@ class TelemetryConfig:
                 buffered_with_size=0
                 if not isinstance(self.metrics, MetricBuffer)
                 else self.metrics._buffer_size,
+                buffered_durations_as_seconds=isinstance(self.metrics, MetricBuffer)
+                and self.metrics._durations_as_seconds,
                 attach_service_name=self.attach_service_name,
                 global_tags=self.global_tags or None,
                 metric_prefix=self.metric_prefix,
@@ -31,6 +31,7 @@ from ._tuning import (
     CustomSlotSupplier,
     FixedSizeSlotSupplier,
     LocalActivitySlotInfo,
+    ManualSlotSupplier,
     ResourceBasedSlotConfig,
     ResourceBasedSlotSupplier,
     ResourceBasedTunerConfig,
@@ -42,7 +43,14 @@ from ._tuning import (
     WorkerTuner,
     WorkflowSlotInfo,
 )
-from ._worker import Worker, WorkerConfig
+from ._worker import (
+    PollerBehavior,
+    PollerBehaviorAutoscaling,
+    PollerBehaviorSimpleMaximum,
+    Worker,
+    WorkerConfig,
+    WorkerDeploymentOptions,
+)
 from ._workflow_instance import (
     UnsandboxedWorkflowRunner,
     WorkflowInstance,
@@ -54,6 +62,7 @@ __all__ = [
     # Primary types
     "Worker",
     "WorkerConfig",
+    "WorkerDeploymentOptions",
     "Replayer",
     "ReplayerConfig",
     "WorkflowReplayResult",
@@ -94,10 +103,15 @@ __all__ = [
     "ActivitySlotInfo",
     "CustomSlotSupplier",
     "LocalActivitySlotInfo",
+    "ManualSlotSupplier",
     "SlotInfo",
     "SlotMarkUsedContext",
     "SlotPermit",
     "SlotReleaseContext",
     "SlotReserveContext",
     "WorkflowSlotInfo",
+    # Poller behavior types
+    "PollerBehavior",
+    "PollerBehaviorSimpleMaximum",
+    "PollerBehaviorAutoscaling",
 ]
@@ -209,20 +209,20 @@ class Replayer:
                 disable_safe_eviction=self._config["disable_safe_workflow_eviction"],
             )
             # Create bridge worker
-            bridge_worker, pusher = temporalio.bridge.worker.Worker.for_replay(
+            bridge_worker, pusher, _ = temporalio.bridge.worker.Worker.for_replay(
                 runtime._core_runtime,
                 temporalio.bridge.worker.WorkerConfig(
                     namespace=self._config["namespace"],
                     task_queue=task_queue,
                     build_id=self._config["build_id"] or load_default_build_id(),
                     identity_override=self._config["identity"],
-                    # Need to tell core whether we want to consider all
-                    # non-determinism exceptions as workflow fail, and whether we do
-                    # per workflow type
-                    nondeterminism_as_workflow_fail=workflow_worker.nondeterminism_as_workflow_fail(),
-                    nondeterminism_as_workflow_fail_for_types=workflow_worker.nondeterminism_as_workflow_fail_for_types(),
+                    # Need to tell core which error types we want to consider
+                    # workflow fail, both for all workflows and per workflow type
+                    workflow_failure_errors=workflow_worker.workflow_failure_errors(),
+                    workflow_types_to_failure_errors=workflow_worker.workflow_types_to_failure_errors(),
                     # All values below are ignored but required by Core
                     max_cached_workflows=2,
+                    disable_sticky_queues=False,
                     tuner=temporalio.bridge.worker.TunerHolder(
                         workflow_slot_supplier=temporalio.bridge.worker.FixedSizeSlotSupplier(
                             2
@@ -234,17 +234,27 @@ class Replayer:
                             1
                         ),
                     ),
-                    max_concurrent_workflow_task_polls=1,
+                    workflow_task_poller_behavior=temporalio.bridge.worker.PollerBehaviorSimpleMaximum(
+                        1
+                    ),
                     nonsticky_to_sticky_poll_ratio=1,
-                    max_concurrent_activity_task_polls=1,
+                    activity_task_poller_behavior=temporalio.bridge.worker.PollerBehaviorSimpleMaximum(
+                        1
+                    ),
                     no_remote_activities=True,
                     sticky_queue_schedule_to_start_timeout_millis=1000,
                     max_heartbeat_throttle_interval_millis=1000,
                     default_heartbeat_throttle_interval_millis=1000,
+                    local_timeout_buffer_for_activities_millis=1000,
                     max_activities_per_second=None,
                     max_task_queue_activities_per_second=None,
+                    max_local_activities_per_second=None,
                     graceful_shutdown_period_millis=0,
                     use_worker_versioning=False,
+                    deployment_options=None,
+                    completion_interceptor=None,
+                    emit_slot_events=False,
+                    poll_watchdog=False,
                 ),
             )
             # Start worker
@@ -37,6 +37,22 @@ class ResourceBasedTunerConfig:
     target_cpu_usage: float
     """A value between 0 and 1 that represents the target (system) CPU usage. This can be set to 1.0
        if desired, but it's recommended to leave some headroom for other processes."""
+    memory_p_gain: Optional[float] = None
+    """Proportional gain of the memory PID controller. Defaults to 5.0."""
+    memory_i_gain: Optional[float] = None
+    """Integral gain of the memory PID controller. Defaults to 0.0."""
+    memory_d_gain: Optional[float] = None
+    """Derivative gain of the memory PID controller. Defaults to 1.0."""
+    memory_output_threshold: Optional[float] = None
+    """Memory PID controller output above which a new slot may be issued. Defaults to 0.25."""
+    cpu_p_gain: Optional[float] = None
+    """Proportional gain of the CPU PID controller. Defaults to 5.0."""
+    cpu_i_gain: Optional[float] = None
+    """Integral gain of the CPU PID controller. Defaults to 0.0."""
+    cpu_d_gain: Optional[float] = None
+    """Derivative gain of the CPU PID controller. Defaults to 1.0."""
+    cpu_output_threshold: Optional[float] = None
+    """CPU PID controller output above which a new slot may be issued. Defaults to 0.05."""
 
 
 @dataclass(frozen=True)
@@ -104,6 +120,9 @@ class SlotReserveContext(Protocol):
     """The build id of the worker that is requesting the reservation."""
     is_sticky: bool
     """True iff this is a reservation for a sticky poll for a workflow task."""
+    num_issued_slots: int
+    """The number of slots that have already been issued by this supplier, whether or not they are
+    currently in use."""
 
 
 # WARNING: This must match Rust worker::WorkflowSlotInfo
@@ -244,8 +263,42 @@ class CustomSlotSupplier(ABC):
         ...
 
 
+class ManualSlotSupplier:
+    """A slot supplier that issues slots only while not blocked, for
+    deterministically starving a worker of slots in tests.
+
+    The same instance may be used for multiple slot types, in which case
+    blocking it blocks all of them.
+    """
+
+    def __init__(self, *, blocked: bool = False) -> None:
+        """Create a manual slot supplier, optionally already blocked."""
+        self._bridge_supplier = temporalio.bridge.worker.BridgeManualSlotSupplier(
+            blocked
+        )
+
+    def permit(self) -> None:
+        """Issue slots to all waiting and future reservations."""
+        self._bridge_supplier.permit()
+
+    def block(self) -> None:
+        """Make future reservations wait until :py:meth:`permit` is called.
+
+        Slots that have already been issued are unaffected.
+        """
+        self._bridge_supplier.block()
+
+    @property
+    def is_blocked(self) -> bool:
+        """Whether reservations currently wait."""
+        return self._bridge_supplier.is_blocked
+
+
 SlotSupplier: TypeAlias = Union[
-    FixedSizeSlotSupplier, ResourceBasedSlotSupplier, CustomSlotSupplier
+    FixedSizeSlotSupplier,
+    ResourceBasedSlotSupplier,
+    CustomSlotSupplier,
+    ManualSlotSupplier,
 ]
 
 
@@ -319,12 +372,22 @@ def _to_bridge_slot_supplier(
             temporalio.bridge.worker.ResourceBasedTunerConfig(
                 slot_supplier.tuner_config.target_memory_usage,
                 slot_supplier.tuner_config.target_cpu_usage,
+                slot_supplier.tuner_config.memory_p_gain,
+                slot_supplier.tuner_config.memory_i_gain,
+                slot_supplier.tuner_config.memory_d_gain,
+                slot_supplier.tuner_config.memory_output_threshold,
+                slot_supplier.tuner_config.cpu_p_gain,
+                slot_supplier.tuner_config.cpu_i_gain,
+                slot_supplier.tuner_config.cpu_d_gain,
+                slot_supplier.tuner_config.cpu_output_threshold,
             ),
         )
     elif isinstance(slot_supplier, CustomSlotSupplier):
         return temporalio.bridge.worker.BridgeCustomSlotSupplier(
             _BridgeSlotSupplierWrapper(slot_supplier)
         )
+    elif isinstance(slot_supplier, ManualSlotSupplier):
+        return slot_supplier._bridge_supplier
     else:
         raise TypeError(f"Unknown slot supplier type: {slot_supplier}")
 
@@ -8,10 +8,21 @@ import hashlib
 import logging
 import sys
 import warnings
+from dataclasses import dataclass
 from datetime import timedelta
-from typing import Any, Awaitable, Callable, List, Optional, Sequence, Type, cast
-
-from typing_extensions import TypedDict
+from typing import (
+    Any,
+    Awaitable,
+    Callable,
+    List,
+    Optional,
+    Sequence,
+    Type,
+    Union,
+    cast,
+)
+
+from typing_extensions import TypeAlias, TypedDict
 
 import temporalio.activity
 import temporalio.api.common.v1
@@ -20,8 +31,10 @@ import temporalio.bridge.proto
 import temporalio.bridge.proto.activity_result
 import temporalio.bridge.proto.activity_task
 import temporalio.bridge.proto.common
+import temporalio.bridge.temporal_sdk_bridge
 import temporalio.bridge.worker
 import temporalio.client
+import temporalio.common
 import temporalio.converter
 import temporalio.exceptions
 import temporalio.runtime
@@ -37,6 +50,80 @@ from .workflow_sandbox import SandboxedWorkflowRunner
 logger = logging.getLogger(__name__)
 
 
+@dataclass(frozen=True)
+class PollerBehaviorSimpleMaximum:
+    """A poller behavior that will attempt to poll as long as a slot is
+    available, up to the provided maximum.
+    """
+
+    maximum: int = 5
+    """The maximum number of pollers at a time."""
+
+    def _to_bridge(self) -> temporalio.bridge.worker.PollerBehavior:
+        return temporalio.bridge.worker.PollerBehaviorSimpleMaximum(
+            maximum=self.maximum
+        )
+
+
+@dataclass(frozen=True)
+class PollerBehaviorAutoscaling:
+    """A poller behavior that will automatically scale the number of pollers
+    based on feedback from the server. A slot must be available before
+    beginning polling.
+
+    .. warning::
+        Autoscaling poller behavior is currently experimental.
+    """
+
+    minimum: int = 1
+    """At least this many poll calls will always be attempted (assuming slots
+    are available)."""
+    maximum: int = 100
+    """At most this many poll calls will ever be open at once. Must be >=
+    ``minimum``."""
+    initial: int = 5
+    """This many polls will be attempted initially before scaling kicks in.
+    Must be between ``minimum`` and ``maximum``."""
+
+    def _to_bridge(self) -> temporalio.bridge.worker.PollerBehavior:
+        return temporalio.bridge.worker.PollerBehaviorAutoscaling(
+            minimum=self.minimum,
+            maximum=self.maximum,
+            initial=self.initial,
+        )
+
+
+PollerBehavior: TypeAlias = Union[
+    PollerBehaviorSimpleMaximum,
+    PollerBehaviorAutoscaling,
+]
+
+
+@dataclass(frozen=True)
+class WorkerDeploymentOptions:
+    """Options for a worker that opts into worker deployment based versioning.
+
+    .. warning::
+        Worker deployment based versioning is currently experimental.
+    """
+
+    deployment_name: str
+    """Name of the deployment this worker belongs to."""
+    build_id: str
+    """Build ID of this worker within the deployment."""
+    default_versioning_behavior: temporalio.common.VersioningBehavior = (
+        temporalio.common.VersioningBehavior.UNSPECIFIED
+    )
+    """Versioning behavior for workflows that do not specify their own."""
+
+    def _to_bridge(self) -> temporalio.bridge.worker.WorkerDeploymentOptions:
+        return temporalio.bridge.worker.WorkerDeploymentOptions(
+            deployment_name=self.deployment_name,
+            build_id=self.build_id,
+            default_versioning_behavior=int(self.default_versioning_behavior),
+        )
+
+
 class Worker:
     """Worker to process workflows and/or activities.
 
@@ -74,7 +161,8 @@ class Worker:
         default_heartbeat_throttle_interval: timedelta = timedelta(seconds=30),
         max_activities_per_second: Optional[float] = None,
         max_task_queue_activities_per_second: Optional[float] = None,
-        graceful_shutdown_timeout: timedelta = timedelta(),
+        max_local_activities_per_second: Optional[float] = None,
+        graceful_shutdown_timeout: Optional[timedelta] = timedelta(),
         workflow_failure_exception_types: Sequence[Type[BaseException]] = [],
         shared_state_manager: Optional[SharedStateManager] = None,
         debug_mode: bool = False,
@@ -82,6 +170,14 @@ class Worker:
         on_fatal_error: Optional[Callable[[BaseException], Awaitable[None]]] = None,
         use_worker_versioning: bool = False,
         disable_safe_workflow_eviction: bool = False,
+        workflow_task_poller_behavior: Optional[PollerBehavior] = None,
+        activity_task_poller_behavior: Optional[PollerBehavior] = None,
+        local_timeout_buffer_for_activities: timedelta = timedelta(seconds=5),
+        deployment_options: Optional[WorkerDeploymentOptions] = None,
+        disable_sticky_queues: bool = False,
+        completion_interceptor: Optional[Callable[[bytes], bytes]] = None,
+        emit_slot_events: bool = False,
+        poll_watchdog: bool = False,
     ) -> None:
         """Create a worker to process workflows and/or activities.
 
@@ -148,6 +244,8 @@ class Worker:
                 while the nonsticky queue will allow one. The minimum for either
                 poller is 1, so if ``max_concurrent_workflow_task_polls`` is 1
                 and sticky queues are enabled, there will be 2 concurrent polls.
+                Must be between 0.0 and 1.0 inclusive. Ignored when sticky
+                queues are disabled, since every poll is then nonsticky.
             max_concurrent_activity_task_polls: Maximum number of concurrent
                 poll activity task requests we will perform at a time on this
                 worker's task queue.
@@ -172,9 +270,14 @@ class Worker:
                 poll request. If multiple workers on the same queue have
                 different values set, they will thrash with the last poller
                 winning.
+            max_local_activities_per_second: Limits the number of local
+                activities per second that this worker will start. Local
+                activities beyond the limit wait for their turn. Default is
+                unbounded.
             graceful_shutdown_timeout: Amount of time after shutdown is called
                 that activities are given to complete before their tasks are
-                cancelled.
+                cancelled. If ``None``, activities are never cancelled by
+                shutdown and it waits for them to complete on their own.
             workflow_failure_exception_types: The types of exceptions that, if a
                 workflow-thrown exception extends, will cause the
                 workflow/update to fail instead of suspending the workflow via
@@ -211,6 +314,34 @@ class Worker:
                 throw ``GeneratorExit`` in coroutines causing them to wake up
                 in different threads and run ``finally`` and other code in the
                 wrong workflow environment.
+            workflow_task_poller_behavior: Specify the behavior of workflow
+                task polling. If set, ``max_concurrent_workflow_task_polls`` is
+                ignored. Defaults to a simple maximum of
+                ``max_concurrent_workflow_task_polls``.
+            activity_task_poller_behavior: Specify the behavior of activity
+                task polling. If set, ``max_concurrent_activity_task_polls`` is
+                ignored. Defaults to a simple maximum of
+                ``max_concurrent_activity_task_polls``. Has no effect if this
+                worker does not poll for activities.
+            local_timeout_buffer_for_activities: How long the worker waits after
+                one of an activity's timeouts elapses locally before timing the
+                activity out itself. This avoids racing the server's own
+                tracking of the timeout.
+            deployment_options: If set, this worker opts into worker deployment
+                based versioning using these options instead of build ID based
+                versioning. Cannot be set with ``use_worker_versioning``.
+                WARNING: This is experimental.
+            disable_sticky_queues: If true, sticky task queues are never
+                scheduled to. This requires ``max_cached_workflows`` to be 0,
+                since sticky queues rely on the workflow cache.
+            completion_interceptor: Called with the serialized bytes of every
+                workflow activation completion, returning the bytes actually
+                sent to the server. Intended for debugging and testing.
+                WARNING: This is experimental.
+            emit_slot_events: If true, every slot reserved, marked used or
+                released is reported to :py:meth:`slot_event_receiver`.
+            poll_watchdog: If true, track when a poll last returned a task for
+                :py:meth:`seconds_since_last_poll`.
         """
         if not activities and not workflows:
             raise ValueError("At least one activity or workflow must be specified")
@@ -218,6 +349,10 @@ class Worker:
             raise ValueError(
                 "build_id must be specified when use_worker_versioning is True"
             )
+        if use_worker_versioning and deployment_options:
+            raise ValueError(
+                "Cannot set both use_worker_versioning and deployment_options"
+            )
 
         # Prepend applicable client interceptors to the given ones
         client_config = client.config()
@@ -256,6 +391,7 @@ class Worker:
             default_heartbeat_throttle_interval=default_heartbeat_throttle_interval,
             max_activities_per_second=max_activities_per_second,
             max_task_queue_activities_per_second=max_task_queue_activities_per_second,
+            max_local_activities_per_second=max_local_activities_per_second,
             graceful_shutdown_timeout=graceful_shutdown_timeout,
             workflow_failure_exception_types=workflow_failure_exception_types,
             shared_state_manager=shared_state_manager,
@@ -264,6 +400,14 @@ class Worker:
             on_fatal_error=on_fatal_error,
             use_worker_versioning=use_worker_versioning,
             disable_safe_workflow_eviction=disable_safe_workflow_eviction,
+            workflow_task_poller_behavior=workflow_task_poller_behavior,
+            activity_task_poller_behavior=activity_task_poller_behavior,
+            local_timeout_buffer_for_activities=local_timeout_buffer_for_activities,
+            deployment_options=deployment_options,
+            disable_sticky_queues=disable_sticky_queues,
+            completion_interceptor=completion_interceptor,
+            emit_slot_events=emit_slot_events,
+            poll_watchdog=poll_watchdog,
         )
         self._started = False
         self._shutdown_event = asyncio.Event()
@@ -358,10 +502,17 @@ class Worker:
                 build_id=build_id or load_default_build_id(),
                 identity_override=identity,
                 max_cached_workflows=max_cached_workflows,
+                disable_sticky_queues=disable_sticky_queues,
                 tuner=bridge_tuner,
-                max_concurrent_workflow_task_polls=max_concurrent_workflow_task_polls,
+                workflow_task_poller_behavior=(
+                    workflow_task_poller_behavior
+                    or PollerBehaviorSimpleMaximum(max_concurrent_workflow_task_polls)
+                )._to_bridge(),
                 nonsticky_to_sticky_poll_ratio=nonsticky_to_sticky_poll_ratio,
-                max_concurrent_activity_task_polls=max_concurrent_activity_task_polls,
+                activity_task_poller_behavior=(
+                    activity_task_poller_behavior
+                    or PollerBehaviorSimpleMaximum(max_concurrent_activity_task_polls)
+                )._to_bridge(),
                 # We have to disable remote activities if a user asks _or_ if we
                 # are not running an activity worker at all. Otherwise shutdown
                 # will not proceed properly.
@@ -375,22 +526,36 @@ class Worker:
                 default_heartbeat_throttle_interval_millis=int(
                     1000 * default_heartbeat_throttle_interval.total_seconds()
                 ),
+                local_timeout_buffer_for_activities_millis=int(
+                    1000 * local_timeout_buffer_for_activities.total_seconds()
+                ),
                 max_activities_per_second=max_activities_per_second,
                 max_task_queue_activities_per_second=max_task_queue_activities_per_second,
-                graceful_shutdown_period_millis=int(
-                    1000 * graceful_shutdown_timeout.total_seconds()
+                max_local_activities_per_second=max_local_activities_per_second,
+                graceful_shutdown_period_millis=(
+                    int(1000 * graceful_shutdown_timeout.total_seconds())
+                    if graceful_shutdown_timeout is not None
+                    else None
                 ),
                 use_worker_versioning=use_worker_versioning,
-                # Need to tell core whether we want to consider all
-                # non-determinism exceptions as workflow fail, and whether we do
-                # per workflow type
-                nondeterminism_as_workflow_fail=self._workflow_worker is not None
-                and self._workflow_worker.nondeterminism_as_workflow_fail(),
-                nondeterminism_as_workflow_fail_for_types=(
-                    self._workflow_worker.nondeterminism_as_workflow_fail_for_types()
+                deployment_options=(
+                    deployment_options._to_bridge() if deployment_options else None
+                ),
+                # Need to tell core which error types we want to consider
+                # workflow fail, both for all workflows and per workflow type
+                workflow_failure_errors=(
+                    self._workflow_worker.workflow_failure_errors()
                     if self._workflow_worker
                     else set()
                 ),
+                workflow_types_to_failure_errors=(
+                    self._workflow_worker.workflow_types_to_failure_errors()
+                    if self._workflow_worker
+                    else {}
+                ),
+                completion_interceptor=completion_interceptor,
+                emit_slot_events=emit_slot_events,
+                poll_watchdog=poll_watchdog,
             ),
         )
 
@@ -421,8 +586,11 @@ class Worker:
 
         Changing the client will make sure the worker starts using it for the
         next calls it makes. However, outstanding client calls will still
-        complete with the existing client. The new client cannot be "lazy" and
-        must be using the same runtime as the current client.
+        complete with the existing client. The new client cannot be "lazy",
+        must be using the same runtime as the current client, and must be on
+        the same namespace as the worker. Unless the worker was given an
+        identity, the new client must also have the same identity as the
+        current one.
         """
         bridge_client = _extract_bridge_client_for_worker(value)
         if self._runtime is not bridge_client.config.runtime:
@@ -430,9 +598,28 @@ class Worker:
                 "New client is not on the same runtime as the existing client"
             )
         assert bridge_client._bridge_client
-        self._bridge_worker.replace_client(bridge_client._bridge_client)
+        self._bridge_worker.replace_client(
+            bridge_client._bridge_client, value.namespace
+        )
         self._config["client"] = value
 
+    def slot_event_receiver(
+        self,
+    ) -> temporalio.bridge.temporal_sdk_bridge.SlotEventReceiver:
+        """Receiver of slot events, see
+        :py:meth:`temporalio.bridge.worker.Worker.slot_event_receiver`.
+
+        Raises:
+            RuntimeError: ``emit_slot_events`` was not set.
+        """
+        return self._bridge_worker.slot_event_receiver()
+
+    def seconds_since_last_poll(self) -> Optional[float]:
+        """Seconds since a poll last returned a task, or since the worker was
+        created if none has yet. ``None`` unless ``poll_watchdog`` was set.
+        """
+        return self._bridge_worker.seconds_since_last_poll()
+
     @property
     def is_running(self) -> bool:
         """Whether the worker is running.
@@ -515,9 +702,14 @@ class Worker:
         # Cancel the shutdown task (safe if already done)
         tasks[0].cancel()
         graceful_timeout = self._config["graceful_shutdown_timeout"]
-        logger.info(
-            f"Beginning worker shutdown, will wait {graceful_timeout} before cancelling activities"
-        )
+        if graceful_timeout is None:
+            logger.info(
+                "Beginning worker shutdown, will wait for activities to complete"
+            )
+        else:
+            logger.info(
+                f"Beginning worker shutdown, will wait {graceful_timeout} before cancelling activities"
+            )
 
         # Initiate core worker shutdown
         self._bridge_worker.initiate_shutdown()
@@ -656,7 +848,8 @@ class WorkerConfig(TypedDict, total=False):
     default_heartbeat_throttle_interval: timedelta
     max_activities_per_second: Optional[float]
     max_task_queue_activities_per_second: Optional[float]
-    graceful_shutdown_timeout: timedelta
+    max_local_activities_per_second: Optional[float]
+    graceful_shutdown_timeout: Optional[timedelta]
     workflow_failure_exception_types: Sequence[Type[BaseException]]
     shared_state_manager: Optional[SharedStateManager]
     debug_mode: bool
@@ -664,6 +857,14 @@ class WorkerConfig(TypedDict, total=False):
     on_fatal_error: Optional[Callable[[BaseException], Awaitable[None]]]
     use_worker_versioning: bool
     disable_safe_workflow_eviction: bool
+    workflow_task_poller_behavior: Optional[PollerBehavior]
+    activity_task_poller_behavior: Optional[PollerBehavior]
+    local_timeout_buffer_for_activities: timedelta
+    deployment_options: Optional[WorkerDeploymentOptions]
+    disable_sticky_queues: bool
+    completion_interceptor: Optional[Callable[[bytes], bytes]]
+    emit_slot_events: bool
+    poll_watchdog: bool
 
 
 _default_build_id: Optional[str] = None
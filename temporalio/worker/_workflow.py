@@ -423,21 +423,27 @@ class _WorkflowWorker:
         else:
             return self._unsandboxed_workflow_runner.create_instance(det)
 
-    def nondeterminism_as_workflow_fail(self) -> bool:
-        return any(
-            issubclass(temporalio.workflow.NondeterminismError, typ)
-            for typ in self._workflow_failure_exception_types
-        )
+    def workflow_failure_errors(self) -> Set[str]:
+        return _failure_error_types(self._workflow_failure_exception_types)
 
-    def nondeterminism_as_workflow_fail_for_types(self) -> Set[str]:
-        return set(
-            k
+    def workflow_types_to_failure_errors(self) -> Dict[str, Set[str]]:
+        return {
+            k: errors
             for k, v in self._workflows.items()
-            if any(
-                issubclass(temporalio.workflow.NondeterminismError, typ)
-                for typ in v.failure_exception_types
-            )
-        )
+            if (errors := _failure_error_types(v.failure_exception_types))
+        }
+
+
+def _failure_error_types(exception_types: Sequence[Type[BaseException]]) -> Set[str]:
+    # Names of the core workflow error types that should fail the workflow
+    # instead of the task given these failure exception types
+    errors = set()
+    if any(
+        issubclass(temporalio.workflow.NondeterminismError, typ)
+        for typ in exception_types
+    ):
+        errors.add("Nondeterminism")
+    return errors
 
 
 class _DeadlockError(Exception):
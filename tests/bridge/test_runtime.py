@@ -38,3 +38,9 @@ def test_bridge_runtime_raise_in_thread():
     thread.join(5)
     assert not thread.is_alive()
     assert type(exc_in_thread) is SomeException
+
+
+def test_bridge_runtime_core_build_info():
+    info = Runtime.core_build_info()
+    assert info["version"] == Runtime.core_version()
+    assert info.keys() == {"version", "git_sha", "bridge_version"}
@@ -1,10 +1,14 @@
+import asyncio
 import logging
 import logging.handlers
 import queue
 import uuid
+from datetime import timedelta
 from typing import List, cast
 from urllib.request import urlopen
 
+import pytest
+
 from temporalio import workflow
 from temporalio.client import Client
 from temporalio.runtime import (
@@ -132,6 +136,52 @@ async def test_runtime_log_forwarding():
     )
 
 
+async def test_runtime_log_forwarding_batching():
+    log_queue: queue.Queue[logging.LogRecord] = queue.Queue()
+    log_queue_list = cast(List[logging.LogRecord], log_queue.queue)
+    logger = logging.getLogger(f"log-{uuid.uuid4()}")
+    logger.addHandler(logging.handlers.QueueHandler(log_queue))
+    logger.setLevel(logging.INFO)
+
+    async def log_queue_len() -> int:
+        return len(log_queue_list)
+
+    with pytest.raises(ValueError, match="max batch size must be at least 1"):
+        Runtime(
+            telemetry=TelemetryConfig(
+                logging=LoggingConfig(
+                    filter=TelemetryFilter(core_level="INFO", other_level="ERROR"),
+                    forwarding=LogForwardingConfig(logger=logger, max_batch_size=0),
+                )
+            )
+        )
+    runtime = Runtime(
+        telemetry=TelemetryConfig(
+            logging=LoggingConfig(
+                filter=TelemetryFilter(core_level="INFO", other_level="ERROR"),
+                forwarding=LogForwardingConfig(
+                    logger=logger,
+                    max_batch_size=2,
+                    max_batch_interval=timedelta(hours=1),
+                ),
+            )
+        )
+    )
+
+    # Held until the batch is full since the interval is so long
+    runtime._core_runtime.write_test_info_log("info1", "extra1")
+    await asyncio.sleep(0.5)
+    assert not log_queue_list
+    runtime._core_runtime.write_test_info_log("info2", "extra2")
+    await assert_eq_eventually(2, log_queue_len)
+    assert log_queue_list[0].message.startswith(
+        "[sdk_core::temporal_sdk_bridge::runtime] info1"
+    )
+    assert log_queue_list[1].message.startswith(
+        "[sdk_core::temporal_sdk_bridge::runtime] info2"
+    )
+
+
 @workflow.defn
 class TaskFailWorkflow:
     @workflow.run
@@ -181,3 +231,8 @@ async def test_runtime_task_fail_log_forwarding(client: Client):
     assert record.levelno == logging.WARNING
     assert record.name == f"{logger.name}-sdk_core::temporal_sdk_core::worker::workflow"
     assert record.temporal_log.fields["run_id"] == handle.result_run_id  # type: ignore
+
+
+def test_runtime_worker_threads_must_be_positive():
+    with pytest.raises(ValueError, match="at least 1"):
+        Runtime(telemetry=TelemetryConfig(), worker_threads=0)
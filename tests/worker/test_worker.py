@@ -2,21 +2,69 @@ from __future__ import annotations
 
 import asyncio
 import concurrent.futures
+import dataclasses
+import time
 import uuid
+from contextlib import asynccontextmanager
 from datetime import timedelta
-from typing import Any, Awaitable, Callable, Optional
+from pathlib import Path
+from typing import (
+    Any,
+    AsyncIterator,
+    Awaitable,
+    Callable,
+    Dict,
+    List,
+    Optional,
+    Set,
+    Tuple,
+)
 
 import pytest
+from google.protobuf import json_format
+from google.protobuf.duration_pb2 import Duration
 
+import temporalio.bridge.client
+import temporalio.bridge.worker
+import temporalio.service
 import temporalio.worker._worker
 from temporalio import activity, workflow
-from temporalio.client import BuildIdOpAddNewDefault, Client, TaskReachabilityType
+from temporalio.api.enums.v1 import EventType
+from temporalio.api.history.v1 import History
+from temporalio.bridge.proto import ActivityTaskCompletion
+from temporalio.bridge.proto.activity_result import ActivityExecutionResult
+from temporalio.bridge.proto.activity_result import Success as ActivitySuccess
+from temporalio.bridge.proto.activity_task import ActivityCancelReason, ActivityTask
+from temporalio.bridge.proto.workflow_activation import WorkflowActivation
+from temporalio.bridge.proto.workflow_commands import (
+    CompleteWorkflowExecution,
+    ScheduleActivity,
+    WorkflowCommand,
+)
+from temporalio.bridge.proto.workflow_completion import (
+    Success,
+    WorkflowActivationCompletion,
+)
+from temporalio.bridge.worker import (
+    PollerBehaviorAutoscaling as BridgePollerBehaviorAutoscaling,
+)
+from temporalio.client import (
+    BuildIdOpAddNewDefault,
+    Client,
+    TaskReachabilityType,
+    WorkflowFailureError,
+    WorkflowHistory,
+)
+from temporalio.common import RetryPolicy
+from temporalio.runtime import MetricBuffer, Runtime, TelemetryConfig
 from temporalio.testing import WorkflowEnvironment
 from temporalio.worker import (
     ActivitySlotInfo,
     CustomSlotSupplier,
     FixedSizeSlotSupplier,
     LocalActivitySlotInfo,
+    ManualSlotSupplier,
+    PollerBehaviorAutoscaling,
     ResourceBasedSlotConfig,
     ResourceBasedSlotSupplier,
     ResourceBasedTunerConfig,
@@ -25,6 +73,7 @@ from temporalio.worker import (
     SlotReleaseContext,
     SlotReserveContext,
     Worker,
+    WorkerDeploymentOptions,
     WorkerTuner,
     WorkflowSlotInfo,
 )
@@ -267,6 +316,18 @@ async def test_can_run_resource_based_worker(client: Client, env: WorkflowEnviro
         await wf1.result()
 
 
+async def test_resource_based_tuner_target_validation(
+    client: Client, env: WorkflowEnvironment
+):
+    tuner = WorkerTuner.create_resource_based(
+        target_memory_usage=1.5,
+        target_cpu_usage=0.5,
+    )
+    with pytest.raises(ValueError) as err:
+        new_worker(client, WaitOnSignalWorkflow, activities=[say_hello], tuner=tuner)
+    assert "target_memory_usage must be within (0.0, 1.0]" in str(err.value)
+
+
 async def test_can_run_composite_tuner_worker(client: Client, env: WorkflowEnvironment):
     resource_based_options = ResourceBasedTunerConfig(0.5, 0.5)
     tuner = WorkerTuner.create_composite(
@@ -303,6 +364,123 @@ async def test_can_run_composite_tuner_worker(client: Client, env: WorkflowEnvir
         await wf1.result()
 
 
+async def test_can_run_autoscaling_polling_worker(
+    client: Client, env: WorkflowEnvironment
+):
+    async with new_worker(
+        client,
+        WaitOnSignalWorkflow,
+        activities=[say_hello],
+        workflow_task_poller_behavior=PollerBehaviorAutoscaling(initial=2),
+        activity_task_poller_behavior=PollerBehaviorAutoscaling(initial=2),
+    ) as w:
+        wf1 = await client.start_workflow(
+            WaitOnSignalWorkflow.run,
+            id=f"autoscaling-polling-{uuid.uuid4()}",
+            task_queue=w.task_queue,
+        )
+        await wf1.signal(WaitOnSignalWorkflow.my_signal, "finish")
+        await wf1.result()
+
+
+async def test_invalid_autoscaling_poller_behavior(
+    client: Client, env: WorkflowEnvironment
+):
+    with pytest.raises(ValueError) as err:
+        new_worker(
+            client,
+            WaitOnSignalWorkflow,
+            activities=[say_hello],
+            activity_task_poller_behavior=PollerBehaviorAutoscaling(
+                minimum=5, initial=2, maximum=10
+            ),
+        )
+    assert "Invalid autoscaling poller behavior" in str(err.value)
+
+
+async def test_cant_specify_worker_versioning_and_deployment_options(
+    client: Client, env: WorkflowEnvironment
+):
+    with pytest.raises(ValueError) as err:
+        new_worker(
+            client,
+            WaitOnSignalWorkflow,
+            activities=[say_hello],
+            build_id="some-build-id",
+            use_worker_versioning=True,
+            deployment_options=WorkerDeploymentOptions(
+                deployment_name="some-deployment", build_id="some-build-id"
+            ),
+        )
+    assert "Cannot set both use_worker_versioning and deployment_options" in str(
+        err.value
+    )
+
+
+async def test_default_heartbeat_throttle_cannot_exceed_max(client: Client):
+    with pytest.raises(ValueError) as err:
+        new_worker(
+            client,
+            WaitOnSignalWorkflow,
+            activities=[say_hello],
+            max_heartbeat_throttle_interval=timedelta(seconds=10),
+            default_heartbeat_throttle_interval=timedelta(seconds=20),
+        )
+    assert "(20000ms) cannot exceed max heartbeat throttle interval (10000ms)" in str(
+        err.value
+    )
+
+
+async def test_nonsticky_to_sticky_poll_ratio_must_be_within_range(client: Client):
+    with pytest.raises(ValueError) as err:
+        new_worker(
+            client,
+            WaitOnSignalWorkflow,
+            activities=[say_hello],
+            nonsticky_to_sticky_poll_ratio=1.5,
+        )
+    assert "must be between 0.0 and 1.0, got 1.5" in str(err.value)
+
+
+async def test_max_local_activities_per_second_must_be_positive(client: Client):
+    with pytest.raises(ValueError) as err:
+        new_worker(
+            client,
+            WaitOnSignalWorkflow,
+            activities=[say_hello],
+            max_local_activities_per_second=0,
+        )
+    assert "Max local activities per second must be greater than zero" in str(
+        err.value
+    )
+
+
+async def test_can_run_worker_without_sticky_queues(
+    client: Client, env: WorkflowEnvironment
+):
+    async with new_worker(
+        client,
+        WaitOnSignalWorkflow,
+        activities=[say_hello],
+        max_cached_workflows=0,
+        disable_sticky_queues=True,
+    ) as w:
+        wf1 = await client.start_workflow(
+            WaitOnSignalWorkflow.run,
+            id=f"no-sticky-{uuid.uuid4()}",
+            task_queue=w.task_queue,
+        )
+        await wf1.signal(WaitOnSignalWorkflow.my_signal, "finish")
+        await wf1.result()
+    with pytest.raises(ValueError, match="Cannot disable sticky queues"):
+        new_worker(
+            client,
+            WaitOnSignalWorkflow,
+            activities=[say_hello],
+            disable_sticky_queues=True,
+        )
+
+
 async def test_cant_specify_max_concurrent_and_tuner(
     client: Client, env: WorkflowEnvironment
 ):
@@ -403,6 +581,7 @@ async def test_custom_slot_supplier(client: Client, env: WorkflowEnvironment):
             assert ctx.task_queue is not None
             assert ctx.worker_identity is not None
             assert ctx.worker_build_id is not None
+            assert ctx.num_issued_slots >= 0
             self.seen_sticky_kinds.add(ctx.is_sticky)
             self.seen_slot_kinds.add(ctx.slot_type)
 
@@ -518,6 +697,1280 @@ async def test_blocking_slot_supplier(client: Client, env: WorkflowEnvironment):
         await asyncio.sleep(1)
 
 
+async def test_manual_slot_supplier(client: Client, env: WorkflowEnvironment):
+    ss = ManualSlotSupplier(blocked=True)
+    tuner = WorkerTuner.create_composite(
+        workflow_supplier=ss,
+        activity_supplier=FixedSizeSlotSupplier(5),
+        local_activity_supplier=FixedSizeSlotSupplier(5),
+    )
+    async with new_worker(
+        client,
+        WaitOnSignalWorkflow,
+        activities=[say_hello],
+        tuner=tuner,
+    ) as w:
+        wf = await client.start_workflow(
+            WaitOnSignalWorkflow.run,
+            id=f"manual-slot-supplier-{uuid.uuid4()}",
+            task_queue=w.task_queue,
+        )
+        # No workflow task can be started while blocked
+        await asyncio.sleep(1)
+        assert ss.is_blocked
+        assert not [
+            e
+            async for e in wf.fetch_history_events()
+            if e.event_type == EventType.EVENT_TYPE_WORKFLOW_TASK_STARTED
+        ]
+        ss.permit()
+        await wf.signal(WaitOnSignalWorkflow.my_signal, "finish")
+        await wf.result()
+
+
+async def test_bridge_worker_validate_detailed_repeatable(client: Client):
+    async with new_worker(client, WaitOnSignalWorkflow) as w:
+        # The worker already validated on start, so these are repeat calls
+        for _ in range(2):
+            validation = await w._bridge_worker.validate_detailed()
+            assert validation.error is None
+            assert validation.namespace_exists
+
+
+async def test_bridge_worker_shutdown_phase(client: Client):
+    async with new_worker(client, WaitOnSignalWorkflow) as w:
+        assert w._bridge_worker.shutdown_phase() == "running"
+        assert not w._bridge_worker.is_shutdown()
+    assert w._bridge_worker.shutdown_phase() == "complete"
+    assert w._bridge_worker.is_shutdown()
+
+
+async def test_bridge_worker_cancelled_poll_resumed(client: Client):
+    async with new_bridge_worker(client) as bridge_worker:
+        canceller = bridge_worker.new_poll_canceller()
+        poll = asyncio.create_task(
+            bridge_worker.poll_workflow_activation_cancellable(canceller)
+        )
+        # Let the poll reach the server before cancelling
+        await asyncio.sleep(0.5)
+        canceller.cancel()
+        assert await poll is None
+        run_id = await start_never_run_workflow(client, bridge_worker)
+        # Resumes the cancelled poll rather than starting another
+        act = await asyncio.wait_for(bridge_worker.poll_workflow_activation(), 10)
+        assert act.run_id == run_id
+        await bridge_worker.complete_workflow_activation(
+            finish_workflow_completion(act)
+        )
+
+
+async def test_bridge_worker_timed_out_poll_resumed(client: Client):
+    async with new_bridge_worker(client) as bridge_worker:
+        task = await bridge_worker.poll_activity_task_timeout(timedelta(seconds=0.5))
+        assert task is None
+        await schedule_never_run_activity(client, bridge_worker)
+        # Resumes the timed out poll rather than starting another
+        task = await asyncio.wait_for(bridge_worker.poll_activity_task(), 10)
+        assert task.start.activity_type == "never_run_activity"
+        await finish_never_run_activity(bridge_worker, task)
+
+
+async def test_bridge_worker_complete_workflow_activations(client: Client):
+    async with new_bridge_worker(client) as bridge_worker:
+        await start_never_run_workflow(client, bridge_worker)
+        act = await bridge_worker.poll_workflow_activation()
+        results = await bridge_worker._ref.complete_workflow_activations(
+            [b"\xff", finish_workflow_completion(act).SerializeToString()]
+        )
+        assert isinstance(results[0], temporalio.bridge.worker.InvalidProtoError)
+        assert results[0].message_type == "WorkflowActivationCompletion"
+        assert results[1] is None
+        assert bridge_worker.poll_stats()["workflow_activations_completed"] == 1
+
+
+async def test_bridge_worker_complete_activity_tasks(client: Client):
+    async with new_bridge_worker(client) as bridge_worker:
+        await schedule_never_run_activity(client, bridge_worker)
+        task = await bridge_worker.poll_activity_task()
+        completion = ActivityTaskCompletion(
+            task_token=task.task_token,
+            result=ActivityExecutionResult(completed=ActivitySuccess()),
+        )
+        results = await bridge_worker._ref.complete_activity_tasks(
+            [b"\xff", completion.SerializeToString()]
+        )
+        assert isinstance(results[0], temporalio.bridge.worker.InvalidProtoError)
+        assert results[0].message_type == "ActivityTaskCompletion"
+        assert results[1] is None
+        assert bridge_worker.poll_stats()["activity_tasks_completed"] == 1
+        act = await bridge_worker.poll_workflow_activation()
+        await bridge_worker.complete_workflow_activation(
+            finish_workflow_completion(act)
+        )
+
+
+async def test_bridge_worker_metrics_snapshot(client: Client):
+    buffer = MetricBuffer(10000)
+    runtime = Runtime(telemetry=TelemetryConfig(metrics=buffer))
+    metrics_client = await Client.connect(
+        client.service_client.config.target_host,
+        namespace=client.namespace,
+        runtime=runtime,
+    )
+    async with new_worker(
+        metrics_client, WaitOnSignalWorkflow, activities=[say_hello]
+    ) as w:
+        handle = await metrics_client.start_workflow(
+            WaitOnSignalWorkflow.run,
+            id=f"workflow-{uuid.uuid4()}",
+            task_queue=w.task_queue,
+        )
+        await handle.signal(WaitOnSignalWorkflow.my_signal, "finish")
+        await handle.result()
+        # Current even though the buffer was never retrieved
+        snapshot = w._bridge_worker.get_metrics_snapshot()
+        assert any(
+            key.startswith("temporal_workflow_completed") and value == 1
+            for key, value in snapshot.items()
+        )
+    # Updates drained for the snapshot are still retrieved
+    assert any(
+        update.metric.name == "temporal_workflow_completed"
+        for update in buffer.retrieve_updates()
+    )
+    assert not w._bridge_worker.get_metrics_snapshot()
+
+
+async def test_bridge_worker_metrics_snapshot_shared_task_queue(client: Client):
+    runtime = Runtime(telemetry=TelemetryConfig(metrics=MetricBuffer(10000)))
+    metrics_client = await Client.connect(
+        client.service_client.config.target_host,
+        namespace=client.namespace,
+        runtime=runtime,
+    )
+
+    def completed(w: Worker) -> int:
+        return sum(
+            int(value)
+            for key, value in w._bridge_worker.get_metrics_snapshot().items()
+            if key.startswith("temporal_workflow_completed")
+        )
+
+    async with new_worker(metrics_client, WaitOnSignalWorkflow) as first:
+        async with new_worker(
+            metrics_client, WaitOnSignalWorkflow, task_queue=first.task_queue
+        ) as second:
+            for _ in range(2):
+                handle = await metrics_client.start_workflow(
+                    WaitOnSignalWorkflow.run,
+                    id=f"workflow-{uuid.uuid4()}",
+                    task_queue=first.task_queue,
+                )
+                await handle.signal(WaitOnSignalWorkflow.my_signal, "finish")
+                await handle.result()
+            # Whichever worker completed them, both see the same totals
+            assert completed(first) == completed(second) == 2
+        # The second worker shutting down leaves the shared snapshot in place
+        assert completed(first) == 2
+    assert not first._bridge_worker.get_metrics_snapshot()
+
+
+async def test_bridge_worker_shutdown_eviction_deadline(client: Client):
+    async with new_bridge_worker(client) as bridge_worker:
+        handle = await client.start_workflow(
+            NeverRunWorkflow.run,
+            id=f"workflow-{uuid.uuid4()}",
+            task_queue=bridge_worker.task_queue,
+        )
+        act = await bridge_worker.poll_workflow_activation()
+        await bridge_worker.complete_workflow_activation(
+            WorkflowActivationCompletion(run_id=act.run_id, successful=Success())
+        )
+        assert bridge_worker.cached_run_ids() == [act.run_id]
+        # Nothing polls the eviction, so the worker cannot drain in time
+        assert not await bridge_worker.initiate_shutdown_with_deadline(
+            timedelta(seconds=0.5)
+        )
+    await handle.terminate()
+
+
+async def test_bridge_worker_effective_tuner_config_resource_based(client: Client):
+    resource_based_options = ResourceBasedTunerConfig(0.5, 0.6, memory_p_gain=4.0)
+    supplier = ResourceBasedSlotSupplier(
+        ResourceBasedSlotConfig(minimum_slots=1, maximum_slots=10),
+        resource_based_options,
+    )
+    tuner = WorkerTuner.create_composite(
+        workflow_supplier=supplier,
+        activity_supplier=supplier,
+        local_activity_supplier=supplier,
+    )
+    async with new_worker(
+        client, WaitOnSignalWorkflow, tuner=tuner, max_local_activities_per_second=5.0
+    ) as w:
+        config = w._bridge_worker.effective_tuner_config()
+    for slot_type in ["workflow", "activity", "local_activity"]:
+        assert config[slot_type]["kind"] == "resource_based"
+        assert config[slot_type]["maximum_slots"] == 10
+        assert config[slot_type]["target_memory_usage"] == 0.5
+        assert config[slot_type]["target_cpu_usage"] == 0.6
+        assert config[slot_type]["memory_p_gain"] == 4.0
+        assert config[slot_type]["cpu_p_gain"] is None
+    assert config["local_activity"]["max_per_second"] == 5.0
+
+
+async def test_bridge_worker_await_eviction(client: Client):
+    async with new_bridge_worker(client) as bridge_worker:
+        with pytest.raises(ValueError, match="neither cached nor recently evicted"):
+            await bridge_worker.await_eviction("unknown-run", timedelta(seconds=1))
+        async with cache_then_evict(client, bridge_worker) as cached:
+            assert not await bridge_worker.await_eviction(
+                cached.run_id, timedelta(milliseconds=100)
+            )
+            await cached.evict()
+            assert await bridge_worker.await_eviction(
+                cached.run_id, timedelta(seconds=1)
+            )
+        # Still resolves once the eviction has been polled and completed
+        assert await bridge_worker.await_eviction(cached.run_id, timedelta(seconds=1))
+
+
+async def test_bridge_worker_completion_timing_callback(client: Client):
+    timings: List[Tuple[str, float]] = []
+    async with new_bridge_worker(client) as bridge_worker:
+        bridge_worker.set_completion_timing_callback(
+            lambda run_id, millis: timings.append((run_id, millis))
+        )
+        async with cache_then_evict(
+            client, bridge_worker, complete_first=False
+        ) as cached:
+            # A command without a variant is rejected by core
+            with pytest.raises(temporalio.bridge.worker.CompletionError):
+                await bridge_worker.complete_workflow_activation(
+                    WorkflowActivationCompletion(
+                        run_id=cached.run_id,
+                        successful=Success(commands=[WorkflowCommand()]),
+                    )
+                )
+            assert not timings
+        assert [timing[0] for timing in timings] == [cached.run_id]
+        assert timings[0][1] >= 0
+
+
+async def test_bridge_worker_effective_poller_behaviors(client: Client):
+    async with new_bridge_worker(
+        client,
+        max_concurrent_workflow_task_polls=3,
+        activity_task_poller_behavior=PollerBehaviorAutoscaling(
+            minimum=2, initial=4, maximum=8
+        ),
+    ) as bridge_worker:
+        assert bridge_worker.effective_poller_behaviors() == {
+            "workflow": {"kind": "simple_maximum", "maximum": 3},
+            "activity": {
+                "kind": "autoscaling",
+                "minimum": 2,
+                "maximum": 8,
+                "initial": 4,
+            },
+        }
+
+
+async def test_bridge_worker_dry_run(client: Client, monkeypatch: pytest.MonkeyPatch):
+    created: List[
+        Tuple[temporalio.bridge.client.Client, temporalio.bridge.worker.WorkerConfig]
+    ] = []
+    create = temporalio.bridge.worker.Worker.create
+
+    def record_create(
+        bridge_client: temporalio.bridge.client.Client,
+        config: temporalio.bridge.worker.WorkerConfig,
+    ) -> temporalio.bridge.worker.Worker:
+        created.append((bridge_client, config))
+        return create(bridge_client, config)
+
+    monkeypatch.setattr(temporalio.bridge.worker.Worker, "create", record_create)
+    async with new_bridge_worker(client):
+        pass
+    bridge_client, config = created[0]
+    temporalio.bridge.worker.Worker.dry_run(bridge_client, config)
+    with pytest.raises(ValueError, match="Invalid autoscaling poller behavior"):
+        temporalio.bridge.worker.Worker.dry_run(
+            bridge_client,
+            dataclasses.replace(
+                config,
+                activity_task_poller_behavior=BridgePollerBehaviorAutoscaling(
+                    minimum=5, maximum=10, initial=2
+                ),
+            ),
+        )
+
+
+async def test_bridge_worker_graceful_shutdown_cancels_activities(client: Client):
+    async with new_bridge_worker(client) as bridge_worker:
+        await schedule_never_run_activity(client, bridge_worker)
+        task = await bridge_worker.poll_activity_task()
+        bridge_worker.initiate_shutdown()
+        cancel = await asyncio.wait_for(bridge_worker.poll_activity_task(), 10)
+        assert cancel.task_token == task.task_token
+        assert cancel.cancel.reason == ActivityCancelReason.WORKER_SHUTDOWN
+        await bridge_worker.complete_activity_task(
+            ActivityTaskCompletion(
+                task_token=task.task_token,
+                result=ActivityExecutionResult(completed=ActivitySuccess()),
+            )
+        )
+
+
+async def test_bridge_worker_graceful_shutdown_unset(client: Client):
+    async with new_bridge_worker(
+        client, graceful_shutdown_timeout=None
+    ) as bridge_worker:
+        await schedule_never_run_activity(client, bridge_worker)
+        task = await bridge_worker.poll_activity_task()
+        bridge_worker.initiate_shutdown()
+        # Without a grace period core leaves the running activity alone
+        assert not await bridge_worker.poll_activity_task_timeout(timedelta(seconds=1))
+        await bridge_worker.complete_activity_task(
+            ActivityTaskCompletion(
+                task_token=task.task_token,
+                result=ActivityExecutionResult(completed=ActivitySuccess()),
+            )
+        )
+
+
+async def test_bridge_worker_opt_polls_none_on_shutdown(client: Client):
+    async with new_bridge_worker(client) as bridge_worker:
+        bridge_worker.initiate_shutdown()
+        assert await bridge_worker.poll_workflow_activation_opt() is None
+        assert await bridge_worker.poll_activity_task_opt() is None
+        # Still None, rather than raising, on later polls
+        assert await bridge_worker.poll_activity_task_opt() is None
+
+
+async def test_bridge_worker_poll_any(client: Client):
+    async with new_bridge_worker(client) as bridge_worker:
+        run_id = await start_never_run_workflow(client, bridge_worker)
+        kind, act = await bridge_worker.poll_any()
+        assert kind == "workflow"
+        assert isinstance(act, WorkflowActivation)
+        assert act.run_id == run_id
+        await bridge_worker.complete_workflow_activation(
+            finish_workflow_completion(act)
+        )
+        await schedule_never_run_activity(client, bridge_worker)
+        # Resumes the activity poll left in flight by the first call
+        kind, task = await bridge_worker.poll_any()
+        assert kind == "activity"
+        assert isinstance(task, ActivityTask)
+        assert task.start.activity_type == "never_run_activity"
+        await finish_never_run_activity(bridge_worker, task)
+
+
+async def test_bridge_worker_pause_resume(client: Client):
+    async with new_bridge_worker(client) as bridge_worker:
+        bridge_worker.pause()
+        assert bridge_worker.is_paused()
+        run_id = await start_never_run_workflow(client, bridge_worker)
+        poll = asyncio.create_task(bridge_worker.poll_workflow_activation())
+        await asyncio.sleep(1)
+        assert not poll.done()
+        bridge_worker.resume()
+        assert not bridge_worker.is_paused()
+        act = await asyncio.wait_for(poll, 10)
+        assert act.run_id == run_id
+        await bridge_worker.complete_workflow_activation(
+            finish_workflow_completion(act)
+        )
+
+
+async def test_bridge_worker_complete_activity_task_failed(client: Client):
+    async with new_bridge_worker(client) as bridge_worker:
+        await schedule_never_run_activity(client, bridge_worker)
+        task = await bridge_worker.poll_activity_task()
+        assert task.start.attempt == 1
+        await bridge_worker.complete_activity_task_failed(
+            task.task_token, "intentional failure", failure_type="TestError"
+        )
+        assert bridge_worker.poll_stats()["activity_tasks_completed"] == 1
+        # The server retries the failed attempt
+        task = await asyncio.wait_for(bridge_worker.poll_activity_task(), 10)
+        assert task.start.attempt == 2
+        await finish_never_run_activity(bridge_worker, task)
+
+
+async def test_bridge_worker_complete_activity_task_cancelled(client: Client):
+    async with new_bridge_worker(client) as bridge_worker:
+        await schedule_never_run_activity(client, bridge_worker)
+        task = await bridge_worker.poll_activity_task()
+        # Shutdown cancels the running activity, which is then acknowledged
+        bridge_worker.initiate_shutdown()
+        cancel = await asyncio.wait_for(bridge_worker.poll_activity_task(), 10)
+        assert cancel.HasField("cancel")
+        await bridge_worker.complete_activity_task_cancelled(task.task_token)
+        assert bridge_worker.poll_stats()["activity_tasks_completed"] == 1
+        assert await bridge_worker.poll_activity_task_opt() is None
+
+
+async def test_bridge_worker_complete_workflow_activation_json(client: Client):
+    async with new_bridge_worker(client) as bridge_worker:
+        await start_never_run_workflow(client, bridge_worker)
+        act = await bridge_worker.poll_workflow_activation()
+        with pytest.raises(ValueError):
+            await bridge_worker.complete_workflow_activation_json("{not json")
+        await bridge_worker.complete_workflow_activation_json(
+            json_format.MessageToJson(finish_workflow_completion(act))
+        )
+        workflow_id = act.jobs[0].initialize_workflow.workflow_id
+        assert await client.get_workflow_handle(workflow_id).result() is None
+
+
+async def test_bridge_worker_completion_interceptor(client: Client):
+    intercepted: List[WorkflowActivationCompletion] = []
+
+    def finish_first(b: bytes) -> bytes:
+        completion = WorkflowActivationCompletion.FromString(b)
+        intercepted.append(completion)
+        if len(intercepted) > 1:
+            return b
+        # Make the first completion finish the workflow
+        completion.successful.commands.append(
+            WorkflowCommand(complete_workflow_execution=CompleteWorkflowExecution())
+        )
+        return completion.SerializeToString()
+
+    async with new_bridge_worker(
+        client, completion_interceptor=finish_first
+    ) as bridge_worker:
+        await start_never_run_workflow(client, bridge_worker)
+        act = await bridge_worker.poll_workflow_activation()
+        await bridge_worker.complete_workflow_activation(
+            WorkflowActivationCompletion(run_id=act.run_id, successful=Success())
+        )
+        assert [completion.run_id for completion in intercepted] == [act.run_id]
+        workflow_id = act.jobs[0].initialize_workflow.workflow_id
+        assert await client.get_workflow_handle(workflow_id).result() is None
+
+
+async def test_bridge_worker_poll_stats(client: Client):
+    async with new_bridge_worker(client) as bridge_worker:
+        assert bridge_worker.poll_stats() == {
+            "workflow_activations_polled": 0,
+            "workflow_activations_completed": 0,
+            "activity_tasks_polled": 0,
+            "activity_tasks_completed": 0,
+            "poll_shutdowns": 0,
+        }
+        await schedule_never_run_activity(client, bridge_worker)
+        task = await bridge_worker.poll_activity_task()
+        await finish_never_run_activity(bridge_worker, task)
+        bridge_worker.initiate_shutdown()
+        with pytest.raises(temporalio.bridge.worker.PollShutdownError):
+            await bridge_worker.poll_activity_task()
+        assert bridge_worker.poll_stats() == {
+            "workflow_activations_polled": 2,
+            "workflow_activations_completed": 2,
+            "activity_tasks_polled": 1,
+            "activity_tasks_completed": 1,
+            "poll_shutdowns": 1,
+        }
+
+
+async def test_bridge_worker_poll_watchdog(client: Client):
+    async with new_bridge_worker(client) as bridge_worker:
+        assert bridge_worker.seconds_since_last_poll() is None
+    async with new_bridge_worker(client, poll_watchdog=True) as bridge_worker:
+        await asyncio.sleep(0.5)
+        since_created = bridge_worker.seconds_since_last_poll()
+        assert since_created is not None and since_created >= 0.5
+        await start_never_run_workflow(client, bridge_worker)
+        act = await bridge_worker.poll_workflow_activation()
+        since_poll = bridge_worker.seconds_since_last_poll()
+        assert since_poll is not None and since_poll < since_created
+        await bridge_worker.complete_workflow_activation(
+            finish_workflow_completion(act)
+        )
+
+
+async def test_bridge_worker_slot_events(client: Client):
+    async with new_bridge_worker(client) as bridge_worker:
+        with pytest.raises(RuntimeError, match="Slot events were not enabled"):
+            bridge_worker.slot_event_receiver()
+    async with new_bridge_worker(client, emit_slot_events=True) as bridge_worker:
+        receiver = bridge_worker.slot_event_receiver()
+        await start_never_run_workflow(client, bridge_worker)
+        act = await bridge_worker.poll_workflow_activation()
+        await bridge_worker.complete_workflow_activation(
+            finish_workflow_completion(act)
+        )
+        seen: Set[str] = set()
+        while seen != {"reserved", "used", "released"}:
+            slot_type, event, issued = await asyncio.wait_for(receiver.next(), 10)
+            assert issued >= 0
+            if slot_type == "workflow":
+                seen.add(event)
+        assert receiver.dropped == 0
+
+
+async def test_bridge_worker_push_history_json(client: Client):
+    history = await finished_workflow_history(client)
+    bridge_worker, pusher, results = temporalio.bridge.worker.Worker.for_replay(
+        Runtime.default()._core_runtime, replay_bridge_worker_config()
+    )
+    with pytest.raises(ValueError, match="Invalid history JSON"):
+        await pusher.push_history_json(history.workflow_id, '{"events": 5}')
+    await pusher.push_history_json(history.workflow_id, history.to_json())
+    pusher.close()
+    await replay_finished_workflows(bridge_worker)
+    workflow_id, success, error, _, _ = await results.next()
+    assert (workflow_id, success, error) == (history.workflow_id, True, None)
+
+
+async def test_bridge_worker_push_history_file(client: Client, tmp_path: Path):
+    json_history = await finished_workflow_history(client)
+    proto_history = await finished_workflow_history(client)
+    json_path = tmp_path / "history.json"
+    json_path.write_text(json_history.to_json())
+    proto_path = tmp_path / "history.binpb"
+    proto_path.write_bytes(History(events=proto_history.events).SerializeToString())
+    bridge_worker, pusher, results = temporalio.bridge.worker.Worker.for_replay(
+        Runtime.default()._core_runtime,
+        replay_bridge_worker_config(),
+        history_buffer_size=2,
+    )
+    with pytest.raises(FileNotFoundError):
+        await pusher.push_history_file("missing", tmp_path / "missing.json")
+    await pusher.push_history_file(json_history.workflow_id, json_path)
+    await pusher.push_history_file(proto_history.workflow_id, proto_path)
+    pusher.close()
+    await replay_finished_workflows(bridge_worker)
+    replayed: Dict[str, bool] = {}
+    for _ in range(2):
+        workflow_id, success, _, _, _ = await results.next()
+        replayed[workflow_id] = success
+    assert replayed == {
+        json_history.workflow_id: True,
+        proto_history.workflow_id: True,
+    }
+
+
+async def test_bridge_worker_replay_pool(client: Client):
+    histories = [await finished_workflow_history(client) for _ in range(2)]
+    with pytest.raises(ValueError, match="concurrency must be at least 1"):
+        temporalio.bridge.worker.Worker.for_replay_pool(
+            Runtime.default()._core_runtime, replay_bridge_worker_config(), 0
+        )
+    bridge_workers, pusher, results = temporalio.bridge.worker.Worker.for_replay_pool(
+        Runtime.default()._core_runtime, replay_bridge_worker_config(), 2
+    )
+    # Round-robined, so each worker replays one history
+    for history in histories:
+        await pusher.push_history(
+            history.workflow_id,
+            History(events=history.events).SerializeToString(),
+        )
+    pusher.close()
+    await asyncio.gather(*(replay_finished_workflows(w) for w in bridge_workers))
+    assert all(w.poll_stats()["workflow_activations_completed"] for w in bridge_workers)
+    replayed: Dict[str, bool] = {}
+    for _ in histories:
+        workflow_id, success, _, _, _ = await results.next()
+        replayed[workflow_id] = success
+    assert replayed == {history.workflow_id: True for history in histories}
+
+
+async def test_bridge_worker_try_push_history(client: Client):
+    history = await finished_workflow_history(client)
+    history_bytes = History(events=history.events).SerializeToString()
+    bridge_worker, pusher, results = temporalio.bridge.worker.Worker.for_replay(
+        Runtime.default()._core_runtime, replay_bridge_worker_config()
+    )
+    # Nothing is replayed until activations are polled, so the single slot
+    # buffer fills up
+    accepted = 0
+    while pusher.try_push_history(history.workflow_id, history_bytes):
+        accepted += 1
+        assert accepted < 3
+    assert accepted >= 1
+    pusher.close()
+    await replay_finished_workflows(bridge_worker)
+    for _ in range(accepted):
+        workflow_id, success, _, _, _ = await results.next()
+        assert (workflow_id, success) == (history.workflow_id, True)
+
+
+async def test_bridge_worker_run_replay_all(client: Client):
+    history = await finished_workflow_history(client)
+    # Replaying finishes the workflow where it originally scheduled an
+    # activity, so this history fails with a nondeterminism error
+    async with new_bridge_worker(client) as bridge_worker:
+        await schedule_never_run_activity(client, bridge_worker)
+        task = await bridge_worker.poll_activity_task()
+        await finish_never_run_activity(bridge_worker, task)
+    nondeterministic_history = await client.get_workflow_handle(
+        task.start.workflow_execution.workflow_id
+    ).fetch_history()
+    bridge_worker, _, _ = temporalio.bridge.worker.Worker.for_replay(
+        Runtime.default()._core_runtime, replay_bridge_worker_config()
+    )
+    summary, _ = await asyncio.gather(
+        bridge_worker.run_replay_all(
+            [
+                (history.workflow_id, History(events=history.events)),
+                (
+                    nondeterministic_history.workflow_id,
+                    History(events=nondeterministic_history.events),
+                ),
+            ]
+        ),
+        replay_finished_workflows(bridge_worker),
+    )
+    assert summary["total"] == 2
+    assert summary["succeeded"] == 1
+    assert summary["failed"] == 1
+    [(workflow_id, error)] = summary["failures"]
+    assert workflow_id == nondeterministic_history.workflow_id
+    assert "Nondeterminism" in error
+
+
+async def test_bridge_worker_push_history_from_server(client: Client):
+    history = await finished_workflow_history(client)
+    assert isinstance(client.service_client, temporalio.service._BridgeServiceClient)
+    bridge_client = client.service_client._bridge_client
+    assert bridge_client
+    bridge_worker, pusher, results = temporalio.bridge.worker.Worker.for_replay(
+        Runtime.default()._core_runtime, replay_bridge_worker_config()
+    )
+    with pytest.raises(temporalio.service.RPCError):
+        await pusher.push_history_from_server(
+            bridge_client, client.namespace, f"missing-{uuid.uuid4()}"
+        )
+    await pusher.push_history_from_server(
+        bridge_client, client.namespace, history.workflow_id, history.run_id
+    )
+    pusher.close()
+    await replay_finished_workflows(bridge_worker)
+    workflow_id, success, _, _, run_id = await results.next()
+    assert (workflow_id, success, run_id) == (history.workflow_id, True, history.run_id)
+
+
+def test_runtime_records_metrics_by_name():
+    buffer = MetricBuffer(10000)
+    core_runtime = Runtime(telemetry=TelemetryConfig(metrics=buffer))._core_runtime
+    core_runtime.counter_add("test_counter", 3, {"tag": "value"})
+    core_runtime.gauge_set("test_gauge", 1.5, {})
+    core_runtime.histogram_record("test_histogram", 25, {})
+    updates = {update.metric.name: update for update in buffer.retrieve_updates()}
+    assert updates["test_counter"].value == 3
+    assert updates["test_counter"].attributes == {"tag": "value"}
+    assert updates["test_gauge"].value == 1.5
+    assert updates["test_histogram"].value == 25
+    # Does nothing rather than raising without metrics
+    Runtime(telemetry=TelemetryConfig())._core_runtime.counter_add(
+        "test_counter", 1, {}
+    )
+
+
+async def test_bridge_worker_describe_task_queue(client: Client):
+    async with new_bridge_worker(client) as bridge_worker:
+        with pytest.raises(ValueError, match="Unknown task queue type"):
+            await bridge_worker.describe_task_queue("nexus")
+        await start_never_run_workflow(client, bridge_worker)
+        act = await bridge_worker.poll_workflow_activation()
+        await bridge_worker.complete_workflow_activation(
+            finish_workflow_completion(act)
+        )
+        description = await bridge_worker.describe_task_queue()
+        assert description["backlog_count"] >= 0
+        assert description["pollers"]
+        assert all("identity" in poller for poller in description["pollers"])
+    bridge_worker, pusher, _ = temporalio.bridge.worker.Worker.for_replay(
+        Runtime.default()._core_runtime, replay_bridge_worker_config()
+    )
+    with pytest.raises(RuntimeError, match="Replay workers have no client"):
+        await bridge_worker.describe_task_queue()
+    pusher.close()
+    await replay_finished_workflows(bridge_worker)
+
+
+async def test_bridge_worker_server_capabilities(client: Client):
+    async with new_bridge_worker(client) as bridge_worker:
+        capabilities = bridge_worker.server_capabilities()
+        assert capabilities
+        assert all(isinstance(flag, bool) for flag in capabilities.values())
+        assert any(capabilities.values())
+    bridge_worker, pusher, _ = temporalio.bridge.worker.Worker.for_replay(
+        Runtime.default()._core_runtime, replay_bridge_worker_config()
+    )
+    with pytest.raises(RuntimeError, match="Replay workers have no client"):
+        bridge_worker.server_capabilities()
+    pusher.close()
+    await replay_finished_workflows(bridge_worker)
+
+
+@workflow.defn
+class ConcurrentLocalActivitiesWorkflow:
+    @workflow.run
+    async def run(self, count: int) -> List[str]:
+        return list(
+            await asyncio.gather(
+                *(
+                    workflow.execute_local_activity(
+                        say_hello,
+                        str(i),
+                        start_to_close_timeout=timedelta(seconds=30),
+                    )
+                    for i in range(count)
+                )
+            )
+        )
+
+
+async def test_max_local_activities_per_second(client: Client):
+    async with new_worker(
+        client,
+        ConcurrentLocalActivitiesWorkflow,
+        activities=[say_hello],
+        max_local_activities_per_second=2,
+    ) as w:
+        start = time.monotonic()
+        result = await client.execute_workflow(
+            ConcurrentLocalActivitiesWorkflow.run,
+            5,
+            id=f"workflow-{uuid.uuid4()}",
+            task_queue=w.task_queue,
+        )
+        # The first starts immediately, then one every half second
+        assert time.monotonic() - start >= 1.9
+    assert result == [f"Hello, {i}!" for i in range(5)]
+
+
+async def test_bridge_worker_cached_workflow_count(client: Client):
+    async with new_bridge_worker(client) as bridge_worker:
+        assert bridge_worker.cached_workflow_count() == 0
+        async with cache_then_evict(client, bridge_worker) as cached:
+            assert bridge_worker.cached_workflow_count() == 1
+            await cached.evict()
+            assert bridge_worker.cached_workflow_count() == 0
+
+
+async def test_bridge_worker_evict_all_cached_workflows(client: Client):
+    async with new_bridge_worker(client) as bridge_worker:
+        workflow_ids: List[str] = []
+        for _ in range(2):
+            run_id = await start_never_run_workflow(client, bridge_worker)
+            act = await bridge_worker.poll_workflow_activation()
+            workflow_ids.append(act.jobs[0].initialize_workflow.workflow_id)
+            await bridge_worker.complete_workflow_activation(
+                WorkflowActivationCompletion(run_id=run_id, successful=Success())
+            )
+        run_ids = set(bridge_worker.cached_run_ids())
+        assert len(run_ids) == 2
+        assert bridge_worker.evict_all_cached_workflows() == 2
+        evicted: Set[str] = set()
+        for _ in range(2):
+            act = await bridge_worker.poll_workflow_activation()
+            assert act.jobs[0].HasField("remove_from_cache")
+            evicted.add(act.run_id)
+            await bridge_worker.complete_workflow_activation(
+                WorkflowActivationCompletion(run_id=act.run_id, successful=Success())
+            )
+        assert evicted == run_ids
+        assert bridge_worker.cached_workflow_count() == 0
+        for workflow_id in workflow_ids:
+            await client.get_workflow_handle(workflow_id).terminate()
+
+
+async def test_bridge_worker_finalize_shutdown_timeout(client: Client):
+    worker = Worker(
+        client,
+        task_queue=f"task-queue-{uuid.uuid4()}",
+        activities=[never_run_activity],
+        workflows=[NeverRunWorkflow],
+    )
+    bridge_worker = worker._bridge_worker
+    await bridge_worker.validate()
+    bridge_worker.initiate_shutdown()
+    assert await bridge_worker.poll_workflow_activation_opt() is None
+    assert await bridge_worker.poll_activity_task_opt() is None
+    # Nothing is in flight, so finalizing completes well within the timeout
+    assert await bridge_worker.finalize_shutdown(timedelta(seconds=10))
+    assert bridge_worker.shutdown_phase() == "complete"
+
+
+async def test_bridge_worker_finalize_shutdown_zero_timeout(client: Client):
+    worker = Worker(
+        client,
+        task_queue=f"task-queue-{uuid.uuid4()}",
+        activities=[never_run_activity],
+        workflows=[NeverRunWorkflow],
+    )
+    bridge_worker = worker._bridge_worker
+    await bridge_worker.validate()
+    handle = await client.start_workflow(
+        NeverRunWorkflow.run,
+        id=f"workflow-{uuid.uuid4()}",
+        task_queue=bridge_worker.task_queue,
+    )
+    # The activation is never completed, so shutdown cannot finish
+    await bridge_worker.poll_workflow_activation()
+    bridge_worker.initiate_shutdown()
+    # A zero timeout is a timeout, not a wait without one
+    assert not await bridge_worker.finalize_shutdown(timedelta(0))
+    await handle.terminate()
+
+
+async def test_bridge_worker_history_buffer_size(client: Client):
+    with pytest.raises(ValueError, match="buffer size must be at least 1"):
+        temporalio.bridge.worker.Worker.for_replay(
+            Runtime.default()._core_runtime,
+            replay_bridge_worker_config(),
+            history_buffer_size=0,
+        )
+    histories = [await finished_workflow_history(client) for _ in range(3)]
+    bridge_worker, pusher, results = temporalio.bridge.worker.Worker.for_replay(
+        Runtime.default()._core_runtime,
+        replay_bridge_worker_config(),
+        history_buffer_size=3,
+    )
+    # All histories fit in the buffer, so pushing does not wait on replay
+    for history in histories:
+        await asyncio.wait_for(
+            pusher.push_history(
+                history.workflow_id,
+                History(events=history.events).SerializeToString(),
+            ),
+            timeout=5,
+        )
+    pusher.close()
+    await replay_finished_workflows(bridge_worker)
+    replayed: Set[str] = set()
+    for _ in histories:
+        workflow_id, success, _, _, _ = await results.next()
+        assert success
+        replayed.add(workflow_id)
+    assert replayed == {history.workflow_id for history in histories}
+
+
+async def test_bridge_worker_replay_result_last_event_id(client: Client):
+    history = await finished_workflow_history(client)
+    bridge_worker, pusher, results = temporalio.bridge.worker.Worker.for_replay(
+        Runtime.default()._core_runtime, replay_bridge_worker_config()
+    )
+    await pusher.push_history(
+        history.workflow_id, History(events=history.events).SerializeToString()
+    )
+    pusher.close()
+    await replay_finished_workflows(bridge_worker)
+    workflow_id, success, _, last_event_id, _ = await results.next()
+    assert (workflow_id, success) == (history.workflow_id, True)
+    assert 0 < last_event_id <= history.events[-1].event_id
+
+
+async def test_bridge_worker_replay_result_run_id(client: Client, tmp_path: Path):
+    histories = [await finished_workflow_history(client) for _ in range(5)]
+    bridge_worker, pusher, results = temporalio.bridge.worker.Worker.for_replay(
+        Runtime.default()._core_runtime,
+        replay_bridge_worker_config(),
+        history_buffer_size=len(histories),
+    )
+    pushed, tried, json_pushed, file_pushed, unset = histories
+    await pusher.push_history(
+        pushed.workflow_id,
+        History(events=pushed.events).SerializeToString(),
+        run_id="pushed-run-id",
+    )
+    assert pusher.try_push_history(
+        tried.workflow_id,
+        History(events=tried.events).SerializeToString(),
+        run_id="tried-run-id",
+    )
+    await pusher.push_history_json(
+        json_pushed.workflow_id, json_pushed.to_json(), run_id="json-run-id"
+    )
+    path = tmp_path / "history.json"
+    path.write_text(file_pushed.to_json())
+    await pusher.push_history_file(
+        file_pushed.workflow_id, path, run_id="file-run-id"
+    )
+    await pusher.push_history(
+        unset.workflow_id, History(events=unset.events).SerializeToString()
+    )
+    pusher.close()
+    await replay_finished_workflows(bridge_worker)
+    run_ids: Dict[str, str] = {}
+    for _ in histories:
+        workflow_id, success, _, _, run_id = await results.next()
+        assert success
+        run_ids[workflow_id] = run_id
+    started = unset.events[0].workflow_execution_started_event_attributes
+    assert run_ids == {
+        pushed.workflow_id: "pushed-run-id",
+        tried.workflow_id: "tried-run-id",
+        json_pushed.workflow_id: "json-run-id",
+        file_pushed.workflow_id: "file-run-id",
+        unset.workflow_id: started.original_execution_run_id,
+    }
+
+
+async def test_resource_based_tuner_mismatch_names_slot_types(client: Client):
+    config = ResourceBasedSlotConfig(minimum_slots=1, maximum_slots=10)
+    tuner = WorkerTuner.create_composite(
+        workflow_supplier=FixedSizeSlotSupplier(5),
+        activity_supplier=ResourceBasedSlotSupplier(
+            config, ResourceBasedTunerConfig(0.5, 0.5)
+        ),
+        local_activity_supplier=ResourceBasedSlotSupplier(
+            config, ResourceBasedTunerConfig(0.5, 0.7)
+        ),
+    )
+    with pytest.raises(ValueError) as err:
+        new_worker(client, WaitOnSignalWorkflow, activities=[say_hello], tuner=tuner)
+    assert (
+        "activity and local activity slot suppliers differ on target_cpu_usage"
+        in str(err.value)
+    )
+
+
+async def test_bridge_worker_poll_transport_error(
+    client: Client, env: WorkflowEnvironment
+):
+    if env.supports_time_skipping:
+        pytest.skip("Java test server does not appear to fail on invalid namespace")
+    config = client.config()
+    config["namespace"] = "does-not-exist"
+    worker = Worker(
+        Client(**config),
+        task_queue=f"task-queue-{uuid.uuid4()}",
+        workflows=[NeverRunWorkflow],
+    )
+    bridge_worker = worker._bridge_worker
+    with pytest.raises(temporalio.bridge.worker.PollTransportError) as err:
+        await asyncio.wait_for(bridge_worker.poll_workflow_activation(), timeout=30)
+    # Still a RuntimeError like poll failures were before
+    assert isinstance(err.value, RuntimeError)
+    assert str(err.value).startswith("Poll failure")
+    bridge_worker.initiate_shutdown()
+    await bridge_worker.finalize_shutdown()
+
+
+@workflow.defn
+class ShortActivityTimeoutWorkflow:
+    @workflow.run
+    async def run(self) -> None:
+        await workflow.execute_activity(
+            "wait_for_local_timeout",
+            start_to_close_timeout=timedelta(seconds=1),
+            retry_policy=RetryPolicy(maximum_attempts=1),
+        )
+
+
+async def test_local_timeout_buffer_for_activities(client: Client):
+    cancelled = asyncio.Event()
+    cancelled_after: List[float] = []
+
+    @activity.defn(name="wait_for_local_timeout")
+    async def wait_for_local_timeout() -> None:
+        start = time.monotonic()
+        await activity.wait_for_cancelled()
+        cancelled_after.append(time.monotonic() - start)
+        cancelled.set()
+
+    async with new_worker(
+        client,
+        ShortActivityTimeoutWorkflow,
+        activities=[wait_for_local_timeout],
+        local_timeout_buffer_for_activities=timedelta(),
+    ) as w:
+        with pytest.raises(WorkflowFailureError):
+            await client.execute_workflow(
+                ShortActivityTimeoutWorkflow.run,
+                id=f"workflow-{uuid.uuid4()}",
+                task_queue=w.task_queue,
+            )
+
+        # Without a buffer the worker times the activity out itself right
+        # after the start to close timeout instead of 5 seconds later
+        await asyncio.wait_for(cancelled.wait(), 3)
+    assert cancelled_after[0] < 3
+
+
+async def test_bridge_worker_workflow_failure_error_names():
+    config = dataclasses.replace(
+        replay_bridge_worker_config(),
+        workflow_failure_errors={"Nondeterminism"},
+        workflow_types_to_failure_errors={"NeverRunWorkflow": {"Bogus"}},
+    )
+    with pytest.raises(ValueError, match="Unknown workflow error type: Bogus"):
+        temporalio.bridge.worker.Worker.for_replay(
+            Runtime.default()._core_runtime, config
+        )
+
+
+async def test_bridge_worker_request_workflow_eviction_reason(client: Client):
+    async with new_bridge_worker(client) as bridge_worker:
+        async with cache_then_evict(client, bridge_worker) as cached:
+            with pytest.raises(ValueError, match="Unknown eviction reason: bogus"):
+                bridge_worker.request_workflow_eviction(cached.run_id, "bogus")
+            # Rejected requests do not evict
+            assert bridge_worker.cached_run_ids() == [cached.run_id]
+            await cached.evict("lang_requested", "evicted by test")
+
+
+async def test_bridge_worker_wait_all_polls_drained(client: Client):
+    async with new_bridge_worker(client) as bridge_worker:
+        poll = asyncio.create_task(bridge_worker.poll_activity_task())
+        # Never drained before shutdown is initiated
+        with pytest.raises(asyncio.TimeoutError):
+            await asyncio.wait_for(bridge_worker.wait_all_polls_drained(), 1)
+        bridge_worker.initiate_shutdown()
+        await asyncio.wait_for(bridge_worker.wait_all_polls_drained(), 10)
+        with pytest.raises(temporalio.bridge.worker.PollShutdownError):
+            await poll
+
+
+async def test_bridge_worker_poll_workflow_activation_with_run_id(client: Client):
+    async with new_bridge_worker(client) as bridge_worker:
+        run_id = await start_never_run_workflow(client, bridge_worker)
+        polled_run_id, b = await bridge_worker.poll_workflow_activation_with_run_id()
+        act = WorkflowActivation.FromString(b)
+        assert polled_run_id == act.run_id == run_id
+        await bridge_worker.complete_workflow_activation(
+            finish_workflow_completion(act)
+        )
+
+
+async def test_bridge_worker_complete_workflow_activation_commands(client: Client):
+    async with new_bridge_worker(client) as bridge_worker:
+        run_id = await start_never_run_workflow(client, bridge_worker)
+        act = await bridge_worker.poll_workflow_activation()
+        workflow_id = act.jobs[0].initialize_workflow.workflow_id
+        with pytest.raises(
+            temporalio.bridge.worker.InvalidProtoError, match="WorkflowCommand"
+        ):
+            await bridge_worker.complete_workflow_activation_commands(
+                run_id, [b"\xff"]
+            )
+        await bridge_worker.complete_workflow_activation_commands(
+            run_id,
+            [
+                WorkflowCommand(
+                    complete_workflow_execution=CompleteWorkflowExecution()
+                ).SerializeToString()
+            ],
+        )
+    assert await client.get_workflow_handle(workflow_id).result() is None
+
+
+async def test_bridge_worker_namespace_task_queue_build_id(client: Client):
+    async with new_bridge_worker(client, build_id="my-build-id") as bridge_worker:
+        assert bridge_worker.namespace == client.namespace
+        assert bridge_worker.task_queue.startswith("task-queue-")
+        assert bridge_worker.build_id == "my-build-id"
+
+
+async def test_bridge_worker_effective_identity(client: Client):
+    async with new_bridge_worker(client) as bridge_worker:
+        assert bridge_worker.effective_identity() == client.identity
+    async with new_bridge_worker(client, identity="my-identity") as bridge_worker:
+        assert bridge_worker.effective_identity() == "my-identity"
+
+
+async def test_bridge_worker_set_build_id(client: Client):
+    async with new_bridge_worker(client, build_id="my-build-id") as bridge_worker:
+        # Setting the current build ID is allowed
+        bridge_worker.set_build_id("my-build-id")
+        with pytest.raises(RuntimeError, match="Cannot change build ID"):
+            bridge_worker.set_build_id("other-build-id")
+        assert bridge_worker.build_id == "my-build-id"
+
+
+async def test_bridge_worker_poll_workflow_activation_with_meta(client: Client):
+    async with new_bridge_worker(client) as bridge_worker:
+        metas: List[Tuple[Optional[bool], bool]] = []
+
+        async def poll() -> WorkflowActivation:
+            polled = await bridge_worker.poll_workflow_activation_with_meta()
+            metas.append(polled[1:])
+            return polled[0]
+
+        async with cache_then_evict(client, bridge_worker, poll=poll):
+            pass
+    # Core does not report the task queue an activation came from, and only
+    # the eviction is for a cached run
+    assert metas == [(None, False), (None, True)]
+
+
+async def test_bridge_worker_poll_activity_task_with_type(client: Client):
+    async with new_bridge_worker(client) as bridge_worker:
+        await schedule_never_run_activity(client, bridge_worker)
+        activity_type, token, b = await bridge_worker.poll_activity_task_with_type()
+        task = ActivityTask.FromString(b)
+        assert activity_type == task.start.activity_type == "never_run_activity"
+        assert token == task.task_token
+        await finish_never_run_activity(bridge_worker, task)
+
+
+async def test_bridge_worker_activation_stream(client: Client):
+    async with new_bridge_worker(client) as bridge_worker:
+        run_id = await start_never_run_workflow(client, bridge_worker)
+        jobs: List[str] = []
+        async for act in bridge_worker.activation_stream():
+            assert act.run_id == run_id
+            jobs.append(act.jobs[0].WhichOneof("variant"))
+            if act.jobs[0].HasField("remove_from_cache"):
+                completion = WorkflowActivationCompletion(
+                    run_id=run_id, successful=Success()
+                )
+            else:
+                completion = finish_workflow_completion(act)
+                # Shutdown ends the stream once the eviction is done, instead
+                # of raising
+                bridge_worker.initiate_shutdown()
+            await bridge_worker.complete_workflow_activation(completion)
+        assert jobs == ["initialize_workflow", "remove_from_cache"]
+
+
+async def test_bridge_worker_finalize_shutdown_forced(client: Client):
+    worker = Worker(
+        client,
+        task_queue=f"task-queue-{uuid.uuid4()}",
+        activities=[never_run_activity],
+    )
+    bridge_worker = worker._bridge_worker
+    await bridge_worker.validate()
+    poll = asyncio.create_task(bridge_worker.poll_activity_task())
+    # The in-flight poll holds the worker, and timing out leaves it usable
+    with pytest.raises(ValueError, match="timed out waiting"):
+        await bridge_worker.finalize_shutdown_forced(timedelta(milliseconds=200))
+    assert bridge_worker.shutdown_phase() == "running"
+    bridge_worker.initiate_shutdown()
+    # Waits for the poll to let go instead of failing immediately
+    await bridge_worker.finalize_shutdown_forced(timedelta(seconds=10))
+    assert bridge_worker.is_shutdown()
+    with pytest.raises(temporalio.bridge.worker.PollShutdownError):
+        await poll
+
+
+async def test_bridge_worker_ping_server(client: Client):
+    async with new_bridge_worker(client) as bridge_worker:
+        millis = await bridge_worker.ping_server()
+        assert 0 <= millis < 10000
+
+
+async def test_bridge_worker_wait_ready(client: Client, env: WorkflowEnvironment):
+    async with new_bridge_worker(client) as bridge_worker:
+        assert await bridge_worker.wait_ready(timedelta(seconds=10))
+        # Can be called again, unlike validate
+        assert await bridge_worker.wait_ready(timedelta(seconds=10))
+    if env.supports_time_skipping:
+        pytest.skip("Java test server does not appear to fail on invalid namespace")
+    config = client.config()
+    config["namespace"] = "does-not-exist"
+    worker = Worker(
+        Client(**config),
+        task_queue=f"task-queue-{uuid.uuid4()}",
+        workflows=[NeverRunWorkflow],
+    )
+    # Validation failures raise rather than waiting out the timeout
+    with pytest.raises(RuntimeError, match="Worker validation failed"):
+        await worker._bridge_worker.wait_ready(timedelta(seconds=10))
+    worker._bridge_worker.initiate_shutdown()
+    await worker._bridge_worker.finalize_shutdown()
+
+
+async def test_bridge_worker_completion_error(client: Client):
+    async with new_bridge_worker(client) as bridge_worker:
+        async with cache_then_evict(
+            client, bridge_worker, complete_first=False
+        ) as cached:
+            # Rejected by core before reaching the server, so there is no code
+            with pytest.raises(temporalio.bridge.worker.CompletionError) as err:
+                await bridge_worker.complete_workflow_activation(
+                    WorkflowActivationCompletion(
+                        run_id=cached.run_id,
+                        successful=Success(commands=[WorkflowCommand()]),
+                    )
+                )
+        assert isinstance(err.value, RuntimeError)
+        assert err.value.code is None
+
+
+async def test_bridge_worker_workflow_autoscaling_poller_behavior(client: Client):
+    async with new_bridge_worker(
+        client,
+        workflow_task_poller_behavior=PollerBehaviorAutoscaling(
+            minimum=2, initial=3, maximum=6
+        ),
+    ) as bridge_worker:
+        assert bridge_worker.effective_poller_behaviors()["workflow"] == {
+            "kind": "autoscaling",
+            "minimum": 2,
+            "maximum": 6,
+            "initial": 3,
+        }
+        # Autoscaled pollers still deliver activations
+        run_id = await start_never_run_workflow(client, bridge_worker)
+        act = await bridge_worker.poll_workflow_activation()
+        assert act.run_id == run_id
+        await bridge_worker.complete_workflow_activation(
+            finish_workflow_completion(act)
+        )
+
+
+async def test_bridge_worker_replace_client(client: Client):
+    other_identity_client = await Client.connect(
+        client.service_client.config.target_host,
+        namespace=client.namespace,
+        identity=f"other-identity-{uuid.uuid4()}",
+    )
+    assert isinstance(client.service_client, temporalio.service._BridgeServiceClient)
+    bridge_client = client.service_client._bridge_client
+    assert bridge_client
+    other_service_client = other_identity_client.service_client
+    assert isinstance(other_service_client, temporalio.service._BridgeServiceClient)
+    other_bridge_client = other_service_client._bridge_client
+    assert other_bridge_client
+    async with new_bridge_worker(client) as bridge_worker:
+        with pytest.raises(ValueError, match="does not match worker namespace"):
+            bridge_worker.replace_client(bridge_client, "other-namespace")
+        with pytest.raises(ValueError, match="does not match worker identity"):
+            bridge_worker.replace_client(other_bridge_client, client.namespace)
+        assert bridge_worker.effective_identity() == client.identity
+        # Forcing skips both checks
+        bridge_worker.replace_client(bridge_client, "other-namespace", force=True)
+        bridge_worker.replace_client(
+            other_bridge_client, client.namespace, force=True
+        )
+        assert bridge_worker.effective_identity() == other_identity_client.identity
+    # An identity override makes the client's identity irrelevant
+    async with new_bridge_worker(client, identity="my-identity") as bridge_worker:
+        bridge_worker.replace_client(other_bridge_client, client.namespace)
+        assert bridge_worker.effective_identity() == "my-identity"
+
+
+async def test_bridge_worker_push_history_json_cli_export():
+    # Exported by the CLI, so enums are PascalCase and int64s are strings
+    history_json = (
+        Path(__file__).with_name("test_replayer_complete_history.json").read_text()
+    )
+    bridge_worker, pusher, results = temporalio.bridge.worker.Worker.for_replay(
+        Runtime.default()._core_runtime, replay_bridge_worker_config()
+    )
+    await pusher.push_history_json("cli-workflow-id", history_json)
+    pusher.close()
+    act = await bridge_worker.poll_workflow_activation()
+    start = act.jobs[0].initialize_workflow
+    assert start.workflow_id == "cli-workflow-id"
+    assert start.workflow_type == "SayHelloWorkflow"
+    # This history does not finish at its first workflow task, so finishing
+    # it here fails the replay, which only needs to have started
+    await bridge_worker.complete_workflow_activation(finish_workflow_completion(act))
+    await replay_finished_workflows(bridge_worker)
+    workflow_id, success, _, _, _ = await results.next()
+    assert (workflow_id, success) == ("cli-workflow-id", False)
+
+
 def create_worker(
     client: Client,
     on_fatal_error: Optional[Callable[[BaseException], Awaitable[None]]] = None,
@@ -531,6 +1984,235 @@ def create_worker(
     )
 
 
+@asynccontextmanager
+async def new_bridge_worker(
+    client: Client, **kwargs: Any
+) -> AsyncIterator[temporalio.bridge.worker.Worker]:
+    """Bridge worker of a worker that is never run, so tests can drive polls
+    and completions directly. Evictions are completed during shutdown.
+    """
+    worker = Worker(
+        client,
+        task_queue=f"task-queue-{uuid.uuid4()}",
+        activities=[never_run_activity],
+        workflows=[NeverRunWorkflow],
+        **kwargs,
+    )
+    bridge_worker = worker._bridge_worker
+    await bridge_worker.validate()
+    try:
+        yield bridge_worker
+    finally:
+        bridge_worker.initiate_shutdown()
+        while act := await bridge_worker.poll_workflow_activation_opt():
+            await bridge_worker.complete_workflow_activation(
+                WorkflowActivationCompletion(run_id=act.run_id, successful=Success())
+            )
+        while await bridge_worker.poll_activity_task_opt():
+            pass
+        await bridge_worker.finalize_shutdown()
+
+
+async def start_never_run_workflow(
+    client: Client, bridge_worker: temporalio.bridge.worker.Worker
+) -> str:
+    handle = await client.start_workflow(
+        NeverRunWorkflow.run,
+        id=f"workflow-{uuid.uuid4()}",
+        task_queue=bridge_worker.task_queue,
+    )
+    assert handle.result_run_id
+    return handle.result_run_id
+
+
+class CachedWorkflow:
+    """Workflow held in the cache by ``cache_then_evict``."""
+
+    def __init__(
+        self,
+        bridge_worker: temporalio.bridge.worker.Worker,
+        poll: Callable[[], Awaitable[WorkflowActivation]],
+        activation: WorkflowActivation,
+    ) -> None:
+        self.bridge_worker = bridge_worker
+        self.poll = poll
+        self.activation = activation
+        self.run_id = activation.run_id
+        self.eviction: Optional[WorkflowActivation] = None
+
+    async def evict(self, *reason_and_message: str) -> WorkflowActivation:
+        """Requests eviction and polls the eviction, which is completed when
+        ``cache_then_evict`` exits.
+        """
+        self.bridge_worker.request_workflow_eviction(self.run_id, *reason_and_message)
+        self.eviction = await self.poll()
+        assert self.eviction.jobs[0].HasField("remove_from_cache")
+        return self.eviction
+
+
+@asynccontextmanager
+async def cache_then_evict(
+    client: Client,
+    bridge_worker: temporalio.bridge.worker.Worker,
+    *,
+    complete_first: bool = True,
+    poll: Optional[Callable[[], Awaitable[WorkflowActivation]]] = None,
+) -> AsyncIterator[CachedWorkflow]:
+    """Starts a workflow and polls its first activation, completing it with no
+    commands unless ``complete_first`` is false. On exit the workflow is
+    evicted unless the body already did so, the eviction is completed and the
+    workflow is terminated. ``poll`` replaces ``poll_workflow_activation``.
+    """
+    poll = poll or bridge_worker.poll_workflow_activation
+    run_id = await start_never_run_workflow(client, bridge_worker)
+    act = await poll()
+    assert act.run_id == run_id
+    if complete_first:
+        await bridge_worker.complete_workflow_activation(
+            WorkflowActivationCompletion(run_id=run_id, successful=Success())
+        )
+    cached = CachedWorkflow(bridge_worker, poll, act)
+    yield cached
+    if cached.eviction is None:
+        await cached.evict()
+    await bridge_worker.complete_workflow_activation(
+        WorkflowActivationCompletion(run_id=run_id, successful=Success())
+    )
+    workflow_id = act.jobs[0].initialize_workflow.workflow_id
+    await client.get_workflow_handle(workflow_id).terminate()
+
+
+async def schedule_never_run_activity(
+    client: Client, bridge_worker: temporalio.bridge.worker.Worker
+) -> str:
+    """Starts a workflow whose first activation schedules the activity, which
+    tests then poll for and pass to ``finish_never_run_activity``
+    """
+    run_id = await start_never_run_workflow(client, bridge_worker)
+    act = await bridge_worker.poll_workflow_activation()
+    assert act.run_id == run_id
+    await bridge_worker.complete_workflow_activation(
+        WorkflowActivationCompletion(
+            run_id=run_id,
+            successful=Success(
+                commands=[
+                    WorkflowCommand(
+                        schedule_activity=ScheduleActivity(
+                            seq=1,
+                            activity_id="1",
+                            activity_type="never_run_activity",
+                            task_queue=bridge_worker.task_queue,
+                            start_to_close_timeout=Duration(seconds=30),
+                            do_not_eagerly_execute=True,
+                        )
+                    )
+                ]
+            ),
+        )
+    )
+    return run_id
+
+
+async def finish_never_run_activity(
+    bridge_worker: temporalio.bridge.worker.Worker, task: ActivityTask
+) -> None:
+    """Completes the activity and then its workflow"""
+    await bridge_worker.complete_activity_task(
+        ActivityTaskCompletion(
+            task_token=task.task_token,
+            result=ActivityExecutionResult(completed=ActivitySuccess()),
+        )
+    )
+    act = await bridge_worker.poll_workflow_activation()
+    await bridge_worker.complete_workflow_activation(finish_workflow_completion(act))
+
+
+def finish_workflow_completion(act: WorkflowActivation) -> WorkflowActivationCompletion:
+    """Completion finishing the workflow so it does not outlive the test"""
+    return WorkflowActivationCompletion(
+        run_id=act.run_id,
+        successful=Success(
+            commands=[
+                WorkflowCommand(complete_workflow_execution=CompleteWorkflowExecution())
+            ]
+        ),
+    )
+
+
+async def finished_workflow_history(client: Client) -> WorkflowHistory:
+    """History of a workflow finished by its first workflow task, which
+    ``replay_finished_workflows`` replays successfully
+    """
+    async with new_bridge_worker(client) as bridge_worker:
+        await start_never_run_workflow(client, bridge_worker)
+        act = await bridge_worker.poll_workflow_activation()
+        await bridge_worker.complete_workflow_activation(
+            finish_workflow_completion(act)
+        )
+    workflow_id = act.jobs[0].initialize_workflow.workflow_id
+    return await client.get_workflow_handle(workflow_id).fetch_history()
+
+
+def replay_bridge_worker_config() -> temporalio.bridge.worker.WorkerConfig:
+    """Same config the replayer gives its bridge worker"""
+    return temporalio.bridge.worker.WorkerConfig(
+        namespace="default",
+        task_queue=f"replay-{uuid.uuid4()}",
+        build_id="test-build-id",
+        identity_override=None,
+        max_cached_workflows=2,
+        disable_sticky_queues=False,
+        tuner=temporalio.bridge.worker.TunerHolder(
+            workflow_slot_supplier=temporalio.bridge.worker.FixedSizeSlotSupplier(2),
+            activity_slot_supplier=temporalio.bridge.worker.FixedSizeSlotSupplier(1),
+            local_activity_slot_supplier=temporalio.bridge.worker.FixedSizeSlotSupplier(
+                1
+            ),
+        ),
+        workflow_task_poller_behavior=temporalio.bridge.worker.PollerBehaviorSimpleMaximum(
+            1
+        ),
+        nonsticky_to_sticky_poll_ratio=1,
+        activity_task_poller_behavior=temporalio.bridge.worker.PollerBehaviorSimpleMaximum(
+            1
+        ),
+        no_remote_activities=True,
+        sticky_queue_schedule_to_start_timeout_millis=1000,
+        max_heartbeat_throttle_interval_millis=1000,
+        default_heartbeat_throttle_interval_millis=1000,
+        local_timeout_buffer_for_activities_millis=1000,
+        max_activities_per_second=None,
+        max_task_queue_activities_per_second=None,
+        max_local_activities_per_second=None,
+        graceful_shutdown_period_millis=0,
+        use_worker_versioning=False,
+        deployment_options=None,
+        workflow_failure_errors=set(),
+        workflow_types_to_failure_errors={},
+        completion_interceptor=None,
+        emit_slot_events=False,
+        poll_watchdog=False,
+    )
+
+
+async def replay_finished_workflows(
+    bridge_worker: temporalio.bridge.worker.Worker,
+) -> None:
+    """Completes activations for histories from ``finished_workflow_history``
+    until the replay worker shuts down, then finalizes it
+    """
+    while act := await bridge_worker.poll_workflow_activation_opt():
+        if any(job.HasField("remove_from_cache") for job in act.jobs):
+            completion = WorkflowActivationCompletion(
+                run_id=act.run_id, successful=Success()
+            )
+        else:
+            completion = finish_workflow_completion(act)
+        await bridge_worker.complete_workflow_activation(completion)
+    bridge_worker.initiate_shutdown()
+    await bridge_worker.finalize_shutdown()
+
+
 class WorkerFailureInjector:
     def __init__(self, worker: Worker) -> None:
         self.workflow = PollFailureInjector(worker, "poll_workflow_activation")
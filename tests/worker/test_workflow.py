@@ -5175,6 +5175,14 @@ async def test_workflow_replace_worker_client(client: Client, env: WorkflowEnvir
             await handle2.terminate()
 
 
+async def test_workflow_replace_worker_client_namespace_mismatch(client: Client):
+    other_client = Client(client.service_client, namespace=f"ns-{uuid.uuid4()}")
+    async with new_worker(client, TickingWorkflow) as worker:
+        with pytest.raises(ValueError, match="does not match worker namespace"):
+            worker.client = other_client
+        assert worker.client is client
+
+
 @activity.defn(dynamic=True)
 async def return_name_activity(args: Sequence[RawValue]) -> str:
     return activity.info().activity_type